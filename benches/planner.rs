@@ -0,0 +1,151 @@
+use chrono::{Duration, Local};
+use cr_tools::logic::{
+    income::IncomeModel,
+    planner::Planner,
+    profile::{ArenaMilestone, Profile},
+    types::{Arena, CardEntry, Rarity},
+};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+
+/// The amount of cards in each benchmarked collection
+const COLLECTION_SIZE: usize = 120;
+
+/// Player profiles in different arenas, with planned progression and all sources of cards
+fn make_profiles() -> Vec<Profile> {
+    let today = Local::now().date_naive();
+
+    vec![
+        Profile::new(Arena::BarbarianBowl),
+        Profile {
+            progression: vec![
+                ArenaMilestone {
+                    arena: Arena::JungleArena,
+                    date: today + Duration::weeks(4),
+                },
+                ArenaMilestone {
+                    arena: Arena::ElectroValley,
+                    date: today + Duration::weeks(12),
+                },
+            ],
+            ..Profile::new(Arena::RoyalArena)
+        },
+        Profile {
+            income: IncomeModel {
+                chests: true,
+                shop: true,
+                pass_royale: true,
+                challenges: true,
+                ..IncomeModel::default()
+            },
+            ..Profile::new(Arena::LegendaryArena)
+        },
+    ]
+}
+
+/// Generates a collection with a mix of rarities, levels and card counts
+fn make_collection(size: usize) -> Vec<CardEntry> {
+    (0..size)
+        .map(|i| {
+            let (rarity, min_level) = match i % 4 {
+                0 => (Rarity::Common, 1),
+                1 => (Rarity::Rare, 3),
+                2 => (Rarity::Epic, 6),
+                _ => (Rarity::Legendary, 9),
+            };
+
+            let mut card = CardEntry::new();
+            card.name = format!("Card {}", i);
+            card.rarity = rarity;
+            card.level = min_level + (i * 7) % (13 - min_level);
+            card.have = (i * 37) % 500;
+            card
+        })
+        .collect()
+}
+
+/// The way the listing used to handle a change: compute, sort and sum everything
+fn full_recompute(cards: &mut [CardEntry], profile: &Profile) {
+    CardEntry::compute_all(cards, profile).unwrap();
+    cards.sort_by(CardEntry::sort_by_remaining(profile));
+    CardEntry::sum_all(cards).unwrap();
+}
+
+fn bench_update(c: &mut Criterion) {
+    let mut group = c.benchmark_group("update");
+
+    for profile in make_profiles().iter() {
+        let planner = Planner::new(make_collection(COLLECTION_SIZE), profile.clone()).unwrap();
+
+        // Change the card in the middle of the list
+        let index = COLLECTION_SIZE / 2;
+        let mut changed = planner.cards()[index].clone();
+        changed.have += 100;
+
+        group.bench_function(format!("full/{:?}", profile.arena), |b| {
+            b.iter_batched(
+                || planner.cards().clone(),
+                |mut cards| {
+                    cards[index] = changed.clone();
+                    full_recompute(&mut cards, profile);
+                    black_box(cards)
+                },
+                BatchSize::SmallInput,
+            )
+        });
+
+        group.bench_function(format!("incremental/{:?}", profile.arena), |b| {
+            b.iter_batched(
+                || Planner::new(planner.cards().clone(), profile.clone()).unwrap(),
+                |mut planner| {
+                    planner.update(changed.clone()).unwrap();
+                    black_box(planner)
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_insert(c: &mut Criterion) {
+    let mut group = c.benchmark_group("insert");
+
+    for profile in make_profiles().iter() {
+        let planner = Planner::new(make_collection(COLLECTION_SIZE), profile.clone()).unwrap();
+
+        let mut new_card = CardEntry::new();
+        new_card.name = "New card".to_owned();
+        new_card.rarity = Rarity::Rare;
+        new_card.level = 9;
+        new_card.have = 120;
+
+        group.bench_function(format!("full/{:?}", profile.arena), |b| {
+            b.iter_batched(
+                || planner.cards().clone(),
+                |mut cards| {
+                    cards.push(new_card.clone());
+                    full_recompute(&mut cards, profile);
+                    black_box(cards)
+                },
+                BatchSize::SmallInput,
+            )
+        });
+
+        group.bench_function(format!("incremental/{:?}", profile.arena), |b| {
+            b.iter_batched(
+                || Planner::new(planner.cards().clone(), profile.clone()).unwrap(),
+                |mut planner| {
+                    planner.insert(new_card.clone()).unwrap();
+                    black_box(planner)
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_update, bench_insert);
+criterion_main!(benches);
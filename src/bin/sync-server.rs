@@ -0,0 +1,22 @@
+//! The self-hosted sync server of cr-tools
+//!
+//! Usage: `sync-server [address] [directory]`,
+//! which defaults to listening on `127.0.0.1:8787` and storing the logs in `sync-data`.
+
+use cr_tools::server::SyncServer;
+use std::env;
+
+fn main() -> std::io::Result<()> {
+    let mut args = env::args().skip(1);
+    let address = args.next().unwrap_or_else(|| "127.0.0.1:8787".to_owned());
+    let directory = args.next().unwrap_or_else(|| "sync-data".to_owned());
+
+    let server = SyncServer::bind(&address, &directory)?;
+    println!(
+        "Syncing the profiles in {} on http://{}",
+        directory, address
+    );
+
+    server.run();
+    Ok(())
+}
@@ -1,19 +1,38 @@
-use super::cards_listing::CardsListing;
+use super::{
+    cards_listing::{CardsListing, Plan},
+    stats::StatsDashboard,
+    today::TodayPanel,
+    update_notice::UpdateNotice,
+};
 use crate::constants;
+use crate::i18n::fill;
 use yew::prelude::*;
 
 /// The root component of cr-tools
-pub struct App;
+pub struct App {
+    link: ComponentLink<Self>,
+
+    /// The sorted cards and the profile of the listing
+    plan: Option<Plan>,
+}
+
+pub enum Msg {
+    Plan(Plan),
+}
 
 impl Component for App {
-    type Message = ();
+    type Message = Msg;
     type Properties = ();
 
-    fn create(_: Self::Properties, _: ComponentLink<Self>) -> Self {
-        Self {}
+    fn create(_: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self { link, plan: None }
     }
 
-    fn update(&mut self, _msg: Self::Message) -> ShouldRender {
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Plan(plan) => self.plan = Some(plan),
+        }
+
         true
     }
 
@@ -22,42 +41,74 @@ impl Component for App {
     }
 
     fn view(&self) -> Html {
+        // The listing shares the language along with its plan
+        let locale = self
+            .plan
+            .as_ref()
+            .map(|plan| plan.locale)
+            .unwrap_or_default();
+        let m = locale.messages();
+
         html! {
             <>
 
+            // Tell about newer versions of the app
+            <UpdateNotice locale=locale />
+
             <h1>{constants::meta::NAME}</h1>
-            {constants::meta::ABOUT}
+            {m.about}
+            <small>{ fill(m.version, &[&constants::meta::VERSION]) }</small>
             <p>
-                <em>{ "Only edit one element at a time - clear name to delete" }</em>
+                <em>{ m.edit_hint }</em>
             </p>
 
+            // What to do today
+            {
+                if let Some(plan) = &self.plan {
+                    html! { <TodayPanel cards=plan.cards.clone() profile=plan.profile.clone() locale=locale /> }
+                } else {
+                    html! {}
+                }
+            }
+
             // The main application
-            <CardsListing />
+            <CardsListing on_plan=self.link.callback(Msg::Plan) />
+
+            // Statistics of the collection
+            {
+                if let Some(plan) = &self.plan {
+                    html! {
+                        <StatsDashboard
+                            cards=plan.cards.clone()
+                            history=plan.history.clone()
+                            locale=locale
+                        />
+                    }
+                } else {
+                    html! {}
+                }
+            }
 
             <p>
-                { "Get the source code "}
-                <a href="https://github.com/Bernd-L/cr-tools">{ "here" }</a>
+                { m.source_code }
+                <a href="https://github.com/Bernd-L/cr-tools">{ m.source_code_link }</a>
             </p>
 
-            <div style=BOXED>
+            <div class="boxed">
                 <h3>{constants::license::license_notice_title()}</h3>
 
                 <p>
                     {constants::license::LICENSE_SHORT} <br />
-                    {"The license: "} <a href=constants::license::LICENSE_URL>{constants::license::LICENSE_URL}</a>
+                    { m.license } <a href=constants::license::LICENSE_URL>{constants::license::LICENSE_URL}</a>
                 </p>
 
                 {constants::license::license_notice_body()}
             </div>
 
             <br />
-            { "\"Clash Royale\" may be a trademark of its owner, with which I'm not affiliated with at all."}
+            { m.trademark }
 
             </>
         }
     }
 }
-
-const BOXED: &str = "
-    max-width: 700px;
-";
@@ -0,0 +1,116 @@
+use super::arena_select::arena_select;
+use crate::i18n::{fill, Locale};
+use crate::logic::{profile::ArenaMilestone, types::Arena};
+use chrono::NaiveDate;
+use yew::prelude::*;
+
+/// Plan the arenas to be reached in the future
+pub struct ArenaProgression {
+    link: ComponentLink<Self>,
+    props: Props,
+    arena: Arena,
+    date: Option<NaiveDate>,
+}
+
+pub enum Msg {
+    Add,
+    Remove(usize),
+    UpdateArena(Arena),
+    UpdateDate(String),
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub progression: Vec<ArenaMilestone>,
+    pub on_change: Callback<Vec<ArenaMilestone>>,
+    pub locale: Locale,
+}
+
+impl Component for ArenaProgression {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            link,
+            props,
+            arena: Arena::LegendaryArena,
+            date: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::UpdateArena(arena) => self.arena = arena,
+            Msg::UpdateDate(date) => self.date = NaiveDate::parse_from_str(&date, "%F").ok(),
+            Msg::Add => {
+                if let Some(date) = self.date {
+                    let mut progression = self.props.progression.clone();
+                    progression.push(ArenaMilestone {
+                        arena: self.arena.clone(),
+                        date,
+                    });
+
+                    // Give the new progression to the listing component
+                    self.props.on_change.emit(progression);
+                }
+            }
+            Msg::Remove(index) => {
+                let mut progression = self.props.progression.clone();
+                progression.remove(index);
+
+                // Give the new progression to the listing component
+                self.props.on_change.emit(progression);
+            }
+        }
+
+        // Re-render
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        // Apply the new progression
+        self.props = props;
+
+        // Re-render with the new progression
+        true
+    }
+
+    fn view(&self) -> Html {
+        let locale = self.props.locale;
+        let m = locale.messages();
+
+        html! {
+            <div class="section">
+                { m.planned_arenas }
+
+                // The milestones planned so far
+                {
+                    for self.props.progression.iter().enumerate().map(|(i, milestone)| html! {
+                        <span>
+                            { fill(m.arena_on, &[&m.arena(&milestone.arena), &locale.format_date(milestone.date)]) }
+                            <button onclick=self.link.callback(move |_| Msg::Remove(i))> {m.remove} </button>
+                            { " " }
+                        </span>
+                    })
+                }
+
+                // The input fields for new milestones
+                { arena_select(&self.arena, locale, self.link.callback(Msg::UpdateArena)) }
+
+                <input
+                    type="date"
+                    aria-label=m.date
+                    oninput=self.link.callback(|i: InputData| Msg::UpdateDate(i.value))
+                />
+
+                <button
+                    onclick=self.link.callback(|_| Msg::Add)
+                    disabled={self.date.is_none()}
+                >
+                    {m.add}
+                </button>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use crate::i18n::Locale;
+use crate::logic::types::Arena;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use yew::prelude::*;
+
+/**
+Renders a dropdown of the arenas (by their localized names), selecting a given one
+
+The training camp is only listed while it's selected,
+as players can't go back to it once they left it.
+*/
+pub fn arena_select(selected: &Arena, locale: Locale, on_select: Callback<Arena>) -> Html {
+    let m = locale.messages();
+
+    let options = Arena::iter()
+        .filter(|arena| *arena != Arena::TrainingCamp || arena == selected)
+        .map(|arena| {
+            html! {
+                <option
+                    value=format!("{:?}", arena)
+                    selected={arena == *selected}
+                >
+                    { m.arena(&arena) }
+                </option>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <select aria-label=m.arena onchange=on_select.reform(|event: ChangeData| {
+            if let ChangeData::Select(data) = event {
+                Arena::from_str(&data.value()).unwrap()
+            } else {
+                panic!("Big oof");
+            }
+        }) >
+            { options }
+        </select>
+    }
+}
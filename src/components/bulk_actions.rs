@@ -0,0 +1,129 @@
+use crate::i18n::{fill, Locale};
+use yew::prelude::*;
+
+/// A change to all selected cards
+#[derive(Clone, PartialEq)]
+pub enum BulkAction {
+    SetLevel(usize),
+    SetDeck(bool),
+    Delete,
+    ClearSelection,
+}
+
+/// Change all selected cards at once
+pub struct BulkActions {
+    link: ComponentLink<Self>,
+    props: Props,
+    level: Option<usize>,
+
+    /// Whether the deletion of the selected cards awaits its confirmation
+    confirming: bool,
+}
+
+pub enum Msg {
+    Apply(BulkAction),
+    UpdateLevel(String),
+    AskDelete,
+    CancelDelete,
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub selected: usize,
+    pub on_action: Callback<BulkAction>,
+    pub locale: Locale,
+}
+
+impl Component for BulkActions {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            link,
+            props,
+            level: None,
+            confirming: false,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::UpdateLevel(level) => self.level = level.parse().ok(),
+            Msg::AskDelete => self.confirming = true,
+            Msg::CancelDelete => self.confirming = false,
+            Msg::Apply(action) => {
+                self.confirming = false;
+                self.props.on_action.emit(action);
+            }
+        }
+
+        // Re-render
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        // Apply the new selection
+        self.props = props;
+
+        // Re-render with the new selection
+        true
+    }
+
+    fn view(&self) -> Html {
+        if self.props.selected == 0 {
+            return html! {};
+        }
+
+        let level = self.level;
+        let m = self.props.locale.messages();
+
+        // Ask before deleting
+        if self.confirming {
+            return html! {
+                <div class="section" role="alert">
+                    { fill(m.confirm_delete_many, &[&self.props.selected]) }
+                    <button onclick=self.link.callback(|_| Msg::Apply(BulkAction::Delete))>
+                        {m.delete}
+                    </button>
+                    <button onclick=self.link.callback(|_| Msg::CancelDelete)>{m.cancel}</button>
+                </div>
+            };
+        }
+
+        html! {
+            <div class="section">
+                { fill(m.selected, &[&self.props.selected]) }
+
+                <input
+                    type="number"
+                    placeholder=m.level.to_lowercase()
+                    aria-label=m.set_level
+                    value={level.map(|l| l.to_string()).unwrap_or_default()}
+                    oninput=self.link.callback(|i: InputData| Msg::UpdateLevel(i.value))
+                />
+                <button
+                    onclick=self.link.batch_callback(move |_| {
+                        level.map(|l| Msg::Apply(BulkAction::SetLevel(l))).into_iter().collect()
+                    })
+                    disabled={level.is_none()}
+                >
+                    {m.set_level}
+                </button>
+
+                <button onclick=self.link.callback(|_| Msg::Apply(BulkAction::SetDeck(true)))>
+                    {m.add_to_deck}
+                </button>
+                <button onclick=self.link.callback(|_| Msg::Apply(BulkAction::SetDeck(false)))>
+                    {m.remove_from_deck}
+                </button>
+                <button onclick=self.link.callback(|_| Msg::AskDelete)>
+                    {m.delete}
+                </button>
+                <button onclick=self.link.callback(|_| Msg::Apply(BulkAction::ClearSelection))>
+                    {m.clear_selection}
+                </button>
+            </div>
+        }
+    }
+}
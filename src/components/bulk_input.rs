@@ -0,0 +1,119 @@
+use crate::i18n::Locale;
+use crate::logic::bulk::{parse_bulk, BulkCard};
+use yew::prelude::*;
+
+/// Enter many cards at once, one per line
+pub struct BulkInput {
+    link: ComponentLink<Self>,
+    props: Props,
+    text: String,
+    errors: Vec<String>,
+}
+
+pub enum Msg {
+    Add,
+    UpdateText(String),
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub on_enter: Callback<Vec<BulkCard>>,
+    pub locale: Locale,
+}
+
+impl Component for BulkInput {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            link,
+            props,
+            text: String::new(),
+            errors: vec![],
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::UpdateText(text) => self.text = text,
+            Msg::Add => {
+                let mut cards = vec![];
+                let mut invalid_lines = vec![];
+                self.errors = vec![];
+
+                let lines = self.text.lines().collect::<Vec<_>>();
+
+                for (number, result) in parse_bulk(&self.text) {
+                    match result {
+                        Ok(card) => cards.push(card),
+                        Err(error) => {
+                            self.errors.push(self.props.locale.messages().error(&error));
+                            invalid_lines.push(lines[number - 1]);
+                        }
+                    }
+                }
+
+                // Give the valid cards to the listing component
+                if !cards.is_empty() {
+                    self.props.on_enter.emit(cards);
+                }
+
+                // Keep the invalid lines for correcting them
+                self.text = invalid_lines.join("\n");
+            }
+        }
+
+        // Re-render
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        // Use a new callback or locale if there is one
+        self.props = props;
+
+        // Re-render (the entered text is kept in this component)
+        true
+    }
+
+    fn view(&self) -> Html {
+        let m = self.props.locale.messages();
+
+        html! {
+            <div class="section">
+                <details>
+                    <summary>{ m.add_many }</summary>
+
+                    <p>
+                        <small>
+                            { m.add_many_help }
+                            { m.add_many_example }
+                            { m.add_many_updates }
+                        </small>
+                    </p>
+
+                    <textarea
+                        rows=8
+                        cols=50
+                        placeholder="Knight, 12, 340"
+                        aria-label=m.add_many
+                        value={self.text.to_owned()}
+                        oninput=self.link.callback(|i: InputData| Msg::UpdateText(i.value))
+                    />
+
+                    <br />
+                    <button
+                        onclick=self.link.callback(|_| Msg::Add)
+                        disabled={self.text.trim().is_empty()}
+                    >
+                        {m.add_all}
+                    </button>
+
+                    <ul>
+                        { for self.errors.iter().map(|error| html! { <li><em>{ error }</em></li> }) }
+                    </ul>
+                </details>
+            </div>
+        }
+    }
+}
@@ -1,22 +1,46 @@
-use crate::logic::types::{CardEntry, Rarity};
+use super::collection::rarity_class;
+use crate::i18n::{fill, Locale, Messages};
+use crate::logic::{
+    calc::LevelCap,
+    catalog::icon_path,
+    types::{CardEntry, Rarity},
+};
 use chrono::{DateTime, Local};
-use float_pretty_print::PrettyPrintFloat;
 use std::str::FromStr;
 use strum::IntoEnumIterator;
+use uuid::Uuid;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, HtmlElement};
 use yew::prelude::*;
 
-/// The root component of cr-tools
+/// Shows a card and its computed values as a row of the table, editing it as a draft
 pub struct CardInfo {
     pub props: Props,
     link: ComponentLink<Self>,
-    card_backup: CardEntry,
-    state: State,
+
+    /// The name field, which gets focused when the draft was saved or dropped
+    name_ref: NodeRef,
+
+    /// The button confirming the deletion
+    confirm_ref: NodeRef,
+
+    /// Whether the deletion of the card awaits its confirmation
+    confirming: bool,
+
+    /// Whether the draft is being saved or dropped by this row
+    closing: bool,
+
+    /// The element to focus after rendering (if any)
+    focus: Option<NodeRef>,
 }
 
 pub enum Msg {
     Update,
     Cancel,
+    AskDelete,
     Delete,
+    Upgrade,
+    Select,
     UpdateName(String),
     UpdateLevel(usize),
     UpdateHave(usize),
@@ -26,14 +50,39 @@ pub enum Msg {
 #[derive(Properties, Clone)]
 pub struct Props {
     pub card: CardEntry,
+
+    /// The unsaved edit of the card (if any)
+    pub draft: Option<CardEntry>,
+
+    /// Whether the card was changed by something else since the edit started
+    #[prop_or_default]
+    pub conflict: bool,
+
+    /// Whether the King level allows the upgrade, or when it will (see `Planner::level_cap`)
+    #[prop_or_default]
+    pub level_cap: Option<LevelCap>,
+
+    /// The King level of the player, which may not allow upgrading the card yet
+    pub king_level: usize,
+
+    /// Whether the card is selected for bulk actions
+    #[prop_or_default]
+    pub selected: bool,
+
+    pub on_edit: Callback<CardEntry>,
+    pub on_cancel: Callback<()>,
     pub on_update: Callback<CardEntry>,
     pub on_delete: Callback<CardEntry>,
+    pub on_upgrade: Callback<Uuid>,
+    pub on_select: Callback<()>,
+
+    pub locale: Locale,
 }
 
 enum State {
     Clean,
     Dirty,
-    Empty,
+    Confirming,
 }
 
 impl Component for CardInfo {
@@ -42,181 +91,337 @@ impl Component for CardInfo {
 
     fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
         Self {
-            card_backup: props.card.clone(),
             props,
             link,
-            state: State::Clean,
+            name_ref: NodeRef::default(),
+            confirm_ref: NodeRef::default(),
+            confirming: false,
+            closing: false,
+            focus: None,
         }
     }
 
     fn update(&mut self, msg: Self::Message) -> ShouldRender {
-        self.state = State::Dirty;
+        let mut card = self.editing().clone();
 
         match msg {
-            Msg::UpdateName(name) => {
-                if name.is_empty() {
-                    self.state = State::Empty;
+            Msg::UpdateName(name) => card.name = name,
+            Msg::UpdateLevel(level) => card.level = level,
+            Msg::UpdateHave(have) => card.have = have,
+            Msg::UpdateRarity(rarity) => card.rarity = rarity,
+            Msg::Update => {
+                // Only save named edits (Enter works in every state)
+                if let State::Dirty = self.state() {
+                    if !card.name.trim().is_empty() {
+                        // Give the new card to the listing component
+                        self.closing = true;
+                        self.props.on_update.emit(card);
+                    }
                 }
-
-                self.props.card.name = name;
+                return false;
             }
-            Msg::UpdateLevel(level) => self.props.card.level = level,
-            Msg::UpdateHave(have) => self.props.card.have = have,
-            Msg::UpdateRarity(rarity) => self.props.card.rarity = rarity,
-            Msg::Update => {
-                // Give the new card to the listing component
-                self.props.on_update.emit(self.props.card.clone());
-
-                // Set as clean
-                self.state = State::Clean;
+            Msg::AskDelete => {
+                // Ask before deleting
+                self.confirming = true;
+                self.focus = Some(self.confirm_ref.clone());
+                return true;
             }
             Msg::Delete => {
-                // Give the new card to the listing component
+                // Give the saved card to delete to the listing component
+                self.confirming = false;
                 self.props.on_delete.emit(self.props.card.clone());
-
-                // Set as clean
-                self.state = State::Clean;
+                return false;
+            }
+            Msg::Upgrade => {
+                // Let the listing component upgrade the saved card
+                self.focus = Some(self.name_ref.clone());
+                self.props.on_upgrade.emit(self.props.card.uuid);
+                return false;
             }
             Msg::Cancel => {
-                // Restore the card from the backup
-                self.props.card = self.card_backup.clone();
+                if self.confirming {
+                    // Keep the card
+                    self.confirming = false;
+                    self.focus = Some(self.name_ref.clone());
+                    return true;
+                }
 
-                // Set as clean
-                self.state = State::Clean;
+                // Drop the draft (Escape works in every state)
+                if self.props.draft.is_some() {
+                    self.closing = true;
+                    self.props.on_cancel.emit(());
+                }
+                return false;
+            }
+            Msg::Select => {
+                // Let the listing component toggle the selection
+                self.props.on_select.emit(());
+                return false;
             }
         }
 
-        // Re-render
-        true
+        // Keep the draft in the listing component, so it survives re-sorting
+        self.props.on_edit.emit(card);
+
+        // Wait for the new draft
+        false
     }
 
     fn change(&mut self, props: Self::Properties) -> ShouldRender {
-        // Apply the new state
-        self.props = props;
+        // Keep the focus in the row once its buttons disappear
+        if self.closing && props.draft.is_none() {
+            self.focus = Some(self.name_ref.clone());
+        }
+        self.closing = false;
 
-        // TODO Handle non-clean states to avoid losing data during edits
+        // Apply the new card and draft (which are matched by UUID)
+        self.props = props;
 
         // Re-render with the new state
         true
     }
 
+    fn rendered(&mut self, _first_render: bool) {
+        if let Some(element) = self.focus.take().and_then(|r| r.cast::<HtmlElement>()) {
+            let _ = element.focus();
+        }
+    }
+
     fn view(&self) -> Html {
-        match self.state {
-            State::Clean => {
-                if let Some(data) = &self.props.card.computed {
-                    // Handle non-legendary cards
-
-                    let get_date = |date: DateTime<Local>| date.date().format("%F");
-
-                    html! {
-                        <>
-
-                        // The input fields for new cards
-                        { self.view_inputs() }
-
-                        // The calculated outputs for the card
-                        <span>{"Need: "} {self.props.card.get_needed_cards()}</span>
-                        <span>{"Remaining: "} {data.cards_remaining}</span>
-                        <span>{"Requests: "} {data.requests_remaining}</span>
-                        <span>{"Weeks: "} {Self::simple_round(data.weeks_remaining.clone())}</span>
-                        <span>{"Days: "} {Self::simple_round(data.days_remaining.clone())}</span>
-                        <span>{"Days in order: "} {Self::simple_round(data.days_in_order.unwrap().clone())}</span>
-                        <span>{"Done on: "} {get_date(data.done_on)}</span>
-                        <span>{"Done in order: "} {get_date(data.done_in_order_on.unwrap())}</span>
-                        <span>{"Gold needed: "} {self.props.card.get_needed_gold_string()}</span>
-
-                        </>
-                    }
-                } else {
-                    // Handle legendary cards
+        let m = self.messages();
+
+        // Save with Enter and cancel with Escape (buttons handle Enter themselves)
+        let on_key = self.link.batch_callback(|event: KeyboardEvent| {
+            let on_button = event
+                .target()
+                .and_then(|target| target.dyn_into::<Element>().ok())
+                .is_some_and(|element| element.tag_name() == "BUTTON");
+
+            match event.key().as_str() {
+                "Enter" if !on_button => vec![Msg::Update],
+                "Escape" => vec![Msg::Cancel],
+                _ => vec![],
+            }
+        });
 
-                    let cards_remaining =
-                        if self.props.card.get_needed_cards() < self.props.card.have {
-                            0
-                        } else {
-                            self.props.card.get_needed_cards() - self.props.card.have
-                        };
-
-                    html! {
-                        <>
-
-                        // The input fields for new cards
-                        { self.view_inputs() }
-
-                        // The calculated outputs for the card
-                        <span>{"Need: "} {self.props.card.get_needed_cards()}</span>
-                        <span>{"Remaining: "} { cards_remaining }</span>
-                        <span>{"Requests: n/a"}</span>
-                        <span>{"Weeks: n/a"}</span>
-                        <span>{"Days: n/a"}</span>
-                        <span>{"Days in order: n/a"}</span>
-                        <span>{"Done on: n/a"}</span>
-                        <span>{"Done in order: n/a"}</span>
-                        <span>{"Gold needed: "} {self.props.card.get_needed_gold_string()}</span>
-
-                        </>
+        html! {
+            <tr class=self.row_class() onkeydown=on_key>
+                <td>
+                    <input
+                        type="checkbox"
+                        aria-label=fill(m.select_card, &[&self.props.card.name])
+                        checked=self.props.selected
+                        onclick=self.link.callback(|_| Msg::Select)
+                    />
+                </td>
+
+                // The input fields of the card
+                { self.view_inputs() }
+
+                // The calculated outputs for the card, or the buttons for its draft or deletion
+                {
+                    match self.state() {
+                        State::Clean => self.view_outputs(),
+                        State::Dirty => self.view_actions(),
+                        State::Confirming => self.view_confirmation(),
                     }
                 }
+
+                <td>
+                    { self.view_upgrade() }
+                    <button
+                        onclick=self.link.callback(|_| Msg::AskDelete)
+                        disabled=self.confirming
+                    >
+                        {m.delete}
+                    </button>
+                </td>
+            </tr>
+        }
+    }
+}
+
+impl CardInfo {
+    /// Returns the draft if there is one, or the saved card otherwise
+    fn editing(&self) -> &CardEntry {
+        self.props.draft.as_ref().unwrap_or(&self.props.card)
+    }
+
+    /// Colors the row by the rarity, highlighting cards ready to be upgraded
+    fn row_class(&self) -> String {
+        let rarity = rarity_class(&self.editing().rarity);
+
+        if self.props.card.is_ready_to_upgrade(self.props.king_level) {
+            format!("{} ready", rarity)
+        } else {
+            rarity.to_string()
+        }
+    }
+
+    fn state(&self) -> State {
+        if self.confirming {
+            State::Confirming
+        } else if self.props.draft.is_some() {
+            State::Dirty
+        } else {
+            State::Clean
+        }
+    }
+
+    /// Renders the calculated values of the card
+    fn view_outputs(&self) -> Html {
+        let locale = self.props.locale;
+        let m = locale.messages();
+        let card = &self.props.card;
+        let needed = locale.format_integer(card.get_needed_cards().unwrap_or_default());
+        let gold_needed = locale.format_gold(card.get_needed_gold().unwrap_or_default());
+
+        if let Some(data) = &card.computed {
+            // Handle cards with predictions
+
+            let get_date = |date: DateTime<Local>| locale.format_date(date.date_naive());
+            let round = |number: f64| locale.format_decimal(number, 2);
+
+            html! {
+                <>
+                <td data-label=m.need>{needed}</td>
+                <td data-label=m.remaining>{locale.format_integer(data.cards_remaining)}</td>
+                { self.view_progress() }
+                <td data-label=m.requests>{locale.format_integer(data.requests_remaining)}</td>
+                <td data-label=m.weeks>{round(data.weeks_remaining)}</td>
+                <td data-label=m.days>{round(data.days_remaining)}</td>
+                <td data-label=m.days_in_order>{round(data.days_in_order.unwrap())}</td>
+                <td data-label=m.done_on>{get_date(data.done_on)}</td>
+                <td data-label=m.planned_order>
+                    {get_date(data.done_in_order_on.unwrap())}
+                    { self.view_level_cap() }
+                </td>
+                <td data-label=m.gold>{gold_needed}</td>
+                </>
             }
-            State::Dirty => {
-                // Handle editing
-                html! {
-                    <>
-
-                    // The input fields for new cards
-                    { self.view_inputs() }
-
-                    // Save edits button
-                    <button onclick=self.link.callback(|_| Msg::Update)> {"Save"} </button>
-
-                    // Cancel button
-                    <button onclick=self.link.callback(|_| Msg::Cancel)> {"Cancel"} </button>
-
-                    // Padding
-                    <span/>
-                    <span/>
-                    <span/>
-                    <span/>
-                    <span/>
-                    <span/>
-                    <span/>
-
-                    </>
-                }
+        } else {
+            // Handle cards which never receive any (like legendary ones without other sources)
+
+            let cards_remaining = card
+                .get_needed_cards()
+                .unwrap_or_default()
+                .saturating_sub(card.have);
+
+            html! {
+                <>
+                <td data-label=m.need>{needed}</td>
+                <td data-label=m.remaining>{locale.format_integer(cards_remaining)}</td>
+                { self.view_progress() }
+                <td data-label=m.requests>{m.not_available}</td>
+                <td data-label=m.weeks>{m.not_available}</td>
+                <td data-label=m.days>{m.not_available}</td>
+                <td data-label=m.days_in_order>{m.not_available}</td>
+                <td data-label=m.done_on>{m.not_available}</td>
+                <td data-label=m.planned_order>{m.not_available}</td>
+                <td data-label=m.gold>{gold_needed}</td>
+                </>
             }
-            State::Empty => {
-                // Handle empty
-                html! {
-                    <>
-
-                    // The input fields for new cards
-                    { self.view_inputs() }
-
-                    // Save edits button
-                    <button onclick=self.link.callback(|_| Msg::Delete)> {"Delete"} </button>
-
-                    // Cancel button
-                    <button onclick=self.link.callback(|_| Msg::Cancel)> {"Cancel"} </button>
-
-                    // Padding
-                    <span/>
-                    <span/>
-                    <span/>
-                    <span/>
-                    <span/>
-                    <span/>
-                    <span/>
-
-                    </>
+        }
+    }
+
+    /// Renders how many of the cards needed for the next level are available
+    fn view_progress(&self) -> Html {
+        let locale = self.props.locale;
+        let m = locale.messages();
+        let progress = self.props.card.get_upgrade_progress().unwrap_or_default();
+        let percent = locale.format_percent(progress, 0);
+
+        html! {
+            <td data-label=m.upgrade_progress>
+                <progress value=progress max=1 aria-label=m.upgrade_progress>{ &percent }</progress>
+                { " " }{ &percent }
+                {
+                    if self.props.card.is_ready_to_upgrade(self.props.king_level) {
+                        html! { <div><strong>{ m.ready_to_upgrade }</strong></div> }
+                    } else {
+                        html! {}
+                    }
                 }
-            }
+            </td>
         }
     }
-}
 
-impl CardInfo {
-    fn simple_round(number: f64) -> String {
-        format!("{:.3}", PrettyPrintFloat(number))
+    /// Renders the button upgrading the card, if it can be upgraded
+    fn view_upgrade(&self) -> Html {
+        let m = self.messages();
+        let card = &self.props.card;
+
+        match self.state() {
+            State::Clean if card.is_ready_to_upgrade(self.props.king_level) => html! {
+                <button
+                    aria-label=fill(m.upgrade_card, &[&card.name, &(card.level + 1)])
+                    onclick=self.link.callback(|_| Msg::Upgrade)
+                >
+                    { m.upgrade }
+                </button>
+            },
+            _ => html! {},
+        }
+    }
+
+    /// Renders the buttons saving and dropping the draft, spanning the outputs
+    fn view_actions(&self) -> Html {
+        let m = self.messages();
+
+        html! {
+            <td colspan=OUTPUT_COLUMNS>
+                <button
+                    onclick=self.link.callback(|_| Msg::Update)
+                    disabled=self.editing().name.trim().is_empty()
+                >
+                    { if self.props.conflict { m.overwrite } else { m.save } }
+                </button>
+                <button onclick=self.link.callback(|_| Msg::Cancel)> {m.cancel} </button>
+
+                // Warn about changes made meanwhile
+                { self.view_conflict() }
+            </td>
+        }
+    }
+
+    /// Renders the question whether to delete the card, spanning the outputs
+    fn view_confirmation(&self) -> Html {
+        let m = self.messages();
+
+        html! {
+            <td colspan=OUTPUT_COLUMNS role="alert">
+                { fill(m.confirm_delete, &[&self.props.card.name]) }
+                <button ref=self.confirm_ref.clone() onclick=self.link.callback(|_| Msg::Delete)>
+                    { m.delete }
+                </button>
+                <button onclick=self.link.callback(|_| Msg::Cancel)> {m.cancel} </button>
+            </td>
+        }
+    }
+
+    fn view_conflict(&self) -> Html {
+        if self.props.conflict {
+            html! { <em role="alert">{ " " } { self.messages().changed_meanwhile }</em> }
+        } else {
+            html! {}
+        }
+    }
+
+    fn messages(&self) -> &'static Messages {
+        self.props.locale.messages()
+    }
+
+    /// Renders a note if the King level prevents the upgrade
+    fn view_level_cap(&self) -> Html {
+        let m = self.messages();
+
+        match &self.props.level_cap {
+            None | Some(LevelCap::Allowed) => html! {},
+            Some(LevelCap::LiftedOn(date)) => html! {
+                <em>{ fill(m.level_cap_until, &[&self.props.locale.format_date(date.date_naive())]) }</em>
+            },
+            Some(LevelCap::Blocked) => html! { <em>{ m.level_cap }</em> },
+        }
     }
 
     fn get_rarities(&self, card: Option<&CardEntry>) -> Html {
@@ -230,48 +435,70 @@ impl CardInfo {
                     false
                 };
 
-                html! {<option value=name selected={should_select}> {name} </option>}
+                html! {<option value=name selected={should_select}> {self.messages().rarity(&rarity)} </option>}
             })
             .collect::<Html>()
     }
 
     /// Renders the input elements
     fn view_inputs(&self) -> Html {
+        let m = self.messages();
+
         html! {
             <>
 
-            <input
-                type="text"
-                placeholder="name"
-                value={self.props.card.name.to_owned()}
-                oninput=self.link.callback(|i: InputData| Msg::UpdateName(i.value))
-            />
-
-            <input
-                type="number"
-                placeholder="level"
-                value={self.props.card.level}
-                oninput=self.link.callback(|i: InputData| Msg::UpdateLevel(i.value.parse::<usize>().unwrap()))
-            />
-
-            <input
-                type="number"
-                placeholder="have"
-                value={self.props.card.have}
-                oninput=self.link.callback(|i: InputData| Msg::UpdateHave(i.value.parse::<usize>().unwrap()))
-            />
-
-            <select onchange=self.link.callback(|event: ChangeData| {
-                if let yew::events::ChangeData::Select(data) = event {
-                    Msg::UpdateRarity(Rarity::from_str(&data.value()).unwrap())
-                } else {
-                    panic!("Big oof");
-                }
-            }) >
-                { self.get_rarities(Some(&self.props.card)) }
-            </select>
+            <td data-label=m.name>
+                <img class="card-icon" src=icon_path(self.editing()) alt="" />
+                <input
+                    type="text"
+                    ref=self.name_ref.clone()
+                    aria-label=m.name
+                    value={self.editing().name.to_owned()}
+                    oninput=self.link.callback(|i: InputData| Msg::UpdateName(i.value))
+                />
+            </td>
+
+            <td data-label=m.level>
+                <input
+                    type="number"
+                    aria-label=m.level
+                    value={self.editing().level}
+                    oninput=self.link.batch_callback(|i: InputData| {
+                        i.value.parse::<usize>().ok().map(Msg::UpdateLevel).into_iter().collect()
+                    })
+                />
+            </td>
+
+            <td data-label=m.have>
+                <input
+                    type="number"
+                    aria-label=m.have
+                    value={self.editing().have}
+                    oninput=self.link.batch_callback(|i: InputData| {
+                        i.value.parse::<usize>().ok().map(Msg::UpdateHave).into_iter().collect()
+                    })
+                />
+            </td>
+
+            <td data-label=m.rarity>
+                <select
+                    aria-label=m.rarity
+                    onchange=self.link.callback(|event: ChangeData| {
+                        if let yew::events::ChangeData::Select(data) = event {
+                            Msg::UpdateRarity(Rarity::from_str(&data.value()).unwrap())
+                        } else {
+                            panic!("Big oof");
+                        }
+                    })
+                >
+                    { self.get_rarities(Some(self.editing())) }
+                </select>
+            </td>
 
             </>
         }
     }
 }
+
+/// The amount of columns with calculated values (after the inputs)
+pub const OUTPUT_COLUMNS: usize = 10;
@@ -1,17 +1,24 @@
+use super::card_info::OUTPUT_COLUMNS;
+use crate::i18n::Locale;
 use crate::logic::types::{CardEntry, Rarity};
 use std::str::FromStr;
 use strum::IntoEnumIterator;
+use web_sys::HtmlElement;
 use yew::prelude::*;
 
-/// Enter information about a card
+/// Enter information about a new card in the last row of the table
 pub struct CardInput {
     link: ComponentLink<Self>,
     card: CardEntry,
     props: Props,
+
+    /// Whether to focus the name field after rendering (to enter the next card)
+    focus_name: bool,
 }
 
 pub enum Msg {
     Create,
+    Reset,
     UpdateName(String),
     UpdateLevel(usize),
     UpdateHave(usize),
@@ -22,6 +29,11 @@ pub enum Msg {
 pub struct Props {
     pub on_create: Callback<CardEntry>,
     pub total_gold: String,
+
+    /// The name field, which the listing component focuses after deleting cards
+    pub name_ref: NodeRef,
+
+    pub locale: Locale,
 }
 
 impl Component for CardInput {
@@ -33,6 +45,7 @@ impl Component for CardInput {
             props,
             link,
             card: CardEntry::new(),
+            focus_name: false,
         }
     }
 
@@ -43,18 +56,22 @@ impl Component for CardInput {
             Msg::UpdateHave(have) => self.card.have = have,
             Msg::UpdateRarity(rarity) => self.card.rarity = rarity,
             Msg::Create => {
+                // Cards need a name (Enter works with an empty one too)
+                if self.card.name.is_empty() {
+                    return false;
+                }
+
                 // Give the new card to the listing component
                 self.props.on_create.emit(self.card.clone());
 
                 // Reset this component
                 self.card = CardEntry::new();
-
-                // Re-render
-                return true;
+                self.focus_name = true;
             }
+            Msg::Reset => self.card = CardEntry::new(),
         }
 
-        // Don't re-render
+        // Re-render
         true
     }
 
@@ -62,64 +79,99 @@ impl Component for CardInput {
         // Use a new callback if there is one
         self.props = props;
 
-        // Don't re-render to avoid losing unsaved state
-        false
+        // Re-render with the new total
+        true
+    }
+
+    fn rendered(&mut self, _first_render: bool) {
+        if self.focus_name {
+            self.focus_name = false;
+
+            if let Some(input) = self.props.name_ref.cast::<HtmlElement>() {
+                let _ = input.focus();
+            }
+        }
     }
 
     fn view(&self) -> Html {
+        let m = self.props.locale.messages();
+
+        // Add with Enter and clear the fields with Escape
+        let on_key = self
+            .link
+            .batch_callback(|event: KeyboardEvent| match event.key().as_str() {
+                "Enter" => vec![Msg::Create],
+                "Escape" => vec![Msg::Reset],
+                _ => vec![],
+            });
+
         html! {
-            <>
+            <tr onkeydown=on_key aria-label=m.new_card>
+                <td/>
 
                 // The input fields for new cards
-                <input
-                    type="text"
-                    placeholder="name"
-                    oninput=self.link.callback(|i: InputData| Msg::UpdateName(i.value))
-                />
-
-                <input
-                    type="number"
-                    placeholder="level"
-                    oninput=self.link.callback(|i: InputData| Msg::UpdateLevel(i.value.parse::<usize>().unwrap()))
-                />
-
-                <input
-                    type="number"
-                    placeholder="have"
-                    oninput=self.link.callback(|i: InputData| Msg::UpdateHave(i.value.parse::<usize>().unwrap()))
-                />
-
-                <select onchange=self.link.callback(|event: ChangeData| {
-                    if let yew::events::ChangeData::Select(data) = event {
-                        Msg::UpdateRarity(Rarity::from_str(&data.value()).unwrap())
-                    } else {
-                        panic!("Big oof");
-                    }
-                }) >
-                    { self.get_rarities() }
-                </select>
+                <td data-label=m.name>
+                    <input
+                        type="text"
+                        ref=self.props.name_ref.clone()
+                        aria-label=m.name
+                        value={self.card.name.to_owned()}
+                        oninput=self.link.callback(|i: InputData| Msg::UpdateName(i.value))
+                    />
+                </td>
+
+                <td data-label=m.level>
+                    <input
+                        type="number"
+                        aria-label=m.level
+                        value={self.card.level}
+                        oninput=self.link.batch_callback(|i: InputData| {
+                            i.value.parse::<usize>().ok().map(Msg::UpdateLevel).into_iter().collect()
+                        })
+                    />
+                </td>
+
+                <td data-label=m.have>
+                    <input
+                        type="number"
+                        aria-label=m.have
+                        value={self.card.have}
+                        oninput=self.link.batch_callback(|i: InputData| {
+                            i.value.parse::<usize>().ok().map(Msg::UpdateHave).into_iter().collect()
+                        })
+                    />
+                </td>
+
+                <td data-label=m.rarity>
+                    <select
+                        aria-label=m.rarity
+                        onchange=self.link.callback(|event: ChangeData| {
+                            if let yew::events::ChangeData::Select(data) = event {
+                                Msg::UpdateRarity(Rarity::from_str(&data.value()).unwrap())
+                            } else {
+                                panic!("Big oof");
+                            }
+                        })
+                    >
+                        { self.get_rarities() }
+                    </select>
+                </td>
 
                 // Save changes button
-                <button
-                    onclick=self.link.callback(|_| Msg::Create)
-                    disabled={self.card.name.is_empty()}
-                >
-                    {"Add"}
-                </button>
-
-                // Padding
-                <span/>
-                <span/>
-                <span/>
-                <span/>
-                <span/>
-                <span/>
-                <span/>
-
-                // Total gold needed
-                <span>{"Total needed: "} {&self.props.total_gold}</span>
-
-            </>
+                <td>
+                    <button
+                        onclick=self.link.callback(|_| Msg::Create)
+                        disabled={self.card.name.is_empty()}
+                    >
+                        {m.add}
+                    </button>
+                </td>
+
+                // Total gold needed (in the gold column)
+                <td colspan={OUTPUT_COLUMNS - 2} />
+                <td>{m.total_needed} {&self.props.total_gold}</td>
+                <td/>
+            </tr>
         }
     }
 }
@@ -130,7 +182,11 @@ impl CardInput {
             .map(|rarity| {
                 let name = format!("{:?}", rarity);
 
-                html! {<option value=name> {name} </option>}
+                html! {
+                    <option value=name selected={self.card.rarity == rarity}>
+                        {self.props.locale.messages().rarity(&rarity)}
+                    </option>
+                }
             })
             .collect::<Html>()
     }
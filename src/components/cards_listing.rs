@@ -1,271 +1,1273 @@
-use super::{card_info::CardInfo, card_input::CardInput};
+use super::{
+    arena_progression::ArenaProgression,
+    arena_select::arena_select,
+    bulk_actions::{BulkAction, BulkActions},
+    bulk_input::BulkInput,
+    card_info::CardInfo,
+    card_input::CardInput,
+    charts::ProgressCharts,
+    collection::CollectionGrid,
+    data_url::{data_url, download},
+    king_tower::KingTower,
+    list_view::ListViewControls,
+    scenarios::ScenarioComparison,
+    sync::SyncPanel,
+    transactions::CardTransactions,
+    trash::Trash,
+};
+use crate::i18n::{fill, Locale, Messages};
 use crate::logic::{
-    events::EventSourcingService,
-    types::{gold_string, Arena, CardEntry, CardEntryV1},
+    bulk::BulkCard,
+    calc::LevelCap,
+    calendar::to_ical,
+    drafts::DraftStore,
+    error::Result,
+    events::{DeletedCard, EventSourcingService, MergeReport},
+    experience::KingProgress,
+    income::IncomeModel,
+    planner::Planner,
+    profile::{ArenaMilestone, Profile},
+    settings::{Layout, ListView, Settings, SortColumn, SyncSettings, Theme},
+    sync::SyncLog,
+    transactions::{CardTransaction, FillRate},
+    types::{
+        get_donation_limit, get_donation_size, get_request_size, Arena, CardEntry, CardEntryV1,
+    },
 };
-use libocc::Event;
-use serde_derive::{Deserialize, Serialize};
+use chrono::Local;
+use gloo_events::EventListener;
+use libocc::{Event, Projector};
+use std::collections::HashSet;
 use std::str::FromStr;
 use strum::IntoEnumIterator;
-use yew::format::Json;
+use uuid::Uuid;
+use wasm_bindgen::JsCast;
+use web_sys::{HtmlElement, StorageEvent};
+use yew::format::{Json, Text};
 use yew::prelude::*;
 use yew::services::storage::{Area, StorageService};
 
 const CARDS_KEY_V1: &str = "cr-tools.state.cards";
 const CARD_EVENTS_KEY: &str = "cr-tools.events.cards";
+const TRANSACTION_EVENTS_KEY: &str = "cr-tools.events.transactions";
 const ARENA_KEY: &str = "cr-tools.state.arena";
+const PROFILE_KEY: &str = "cr-tools.state.profile";
+const SETTINGS_KEY: &str = "cr-tools.settings";
 
 /// The listing of the cards to keep track of
 pub struct CardsListing {
     link: ComponentLink<Self>,
+    props: Props,
     storage: StorageService,
     events: EventSourcingService,
     state: State,
+    settings: Settings,
+
+    /// The name field of the new card, which gets focused after deleting cards
+    new_card_ref: NodeRef,
+
+    /// Receives the changes saved by other tabs (until it's dropped)
+    _storage_listener: EventListener,
 }
 
-#[derive(Serialize, Deserialize)]
 pub struct State {
-    cards: Vec<CardEntry>,
-    arena: Arena,
+    planner: Planner,
+    error: Option<String>,
+
+    /// The cards selected for bulk actions
+    selected: HashSet<Uuid>,
+
+    /// The unsaved edits of cards
+    drafts: DraftStore,
+
+    /// Whether to focus the new card after rendering (because the focused row was deleted)
+    focus_new_card: bool,
+
+    /// The deleted cards (read from the event log whenever it changes)
+    trash: Vec<DeletedCard>,
 }
 
 pub enum Msg {
     Create(CardEntry),
-    Update(usize, CardEntry),
-    Delete(usize, CardEntry),
+    Edit(CardEntry),
+    Cancel(Uuid),
+    Update(CardEntry),
+    Delete(CardEntry),
+    Restore(CardEntry),
+    Upgrade(Uuid),
     SetArena(Arena),
+    SetTrophies(usize),
+    SetIncome(IncomeModel),
+    SetProgression(Vec<ArenaMilestone>),
+    SetKing(KingProgress),
+    LogTransaction(CardTransaction),
+    SetView(ListView),
+    SortBy(SortColumn),
+    BulkEnter(Vec<BulkCard>),
+    Bulk(BulkAction),
+    ToggleSelected(Uuid),
+    SelectListed(bool),
+    SetSync(SyncSettings),
+    SetLocale(Locale),
+    SetTheme(Theme),
+    SetLayout(Layout),
+    ExportCalendar,
+
+    /// The event logs of the sync server were received
+    Pulled(SyncLog),
+
+    /// Another tab changed the value of a key in localStorage (or cleared all of them)
+    StorageChanged(Option<String>),
+}
+
+impl Msg {
+    /// Checks if the message can change the plan shared with other components (see `Plan`)
+    fn changes_plan(&self) -> bool {
+        !matches!(
+            self,
+            Msg::Edit(_)
+                | Msg::Cancel(_)
+                | Msg::SetView(_)
+                | Msg::SortBy(_)
+                | Msg::ToggleSelected(_)
+                | Msg::SelectListed(_)
+                | Msg::SetSync(_)
+                | Msg::SetTheme(_)
+                | Msg::SetLayout(_)
+                | Msg::ExportCalendar
+        )
+    }
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    /// Receives the plan whenever it changes
+    pub on_plan: Callback<Plan>,
+}
+
+/// The state of the listing shared with other components
+#[derive(Clone)]
+pub struct Plan {
+    /// The sorted cards
+    pub cards: Vec<CardEntry>,
+
+    pub profile: Profile,
+
+    /// The event log of the cards
+    pub history: Projector<CardEntry>,
+
+    /// The language of the user interface
+    pub locale: Locale,
 }
 
 impl Component for CardsListing {
     type Message = Msg;
-    type Properties = ();
+    type Properties = Props;
 
-    fn create(_: Self::Properties, link: ComponentLink<Self>) -> Self {
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
         // Get a reference to localStorage
         let mut storage = StorageService::new(Area::Local).expect("Cannot use localStorage");
 
+        // A problem with the saved data, which is shown once the language is known
+        let mut load_error = None;
+
         // Load the cards from localStorage
         let events = {
             if let Json(Ok(events)) = storage.restore(CARD_EVENTS_KEY) {
-                // Load the event log from localStorage
-                EventSourcingService::load(events)
+                // Load the event logs from localStorage
+                let transactions =
+                    if let Json(Ok(transactions)) = storage.restore(TRANSACTION_EVENTS_KEY) {
+                        transactions
+                    } else {
+                        Projector::new()
+                    };
+
+                EventSourcingService::load(events, transactions)
             } else if let Json(Ok(old_cards)) = storage.restore(CARDS_KEY_V1) {
                 // Tell the compiler about the type
                 // TODO improve or report bug/suggestion to rust lang
                 let old_cards: Vec<CardEntryV1> = old_cards;
 
                 // Convert the old format to the new one
-                let events = EventSourcingService::migrate_from_v1(old_cards).unwrap();
+                match EventSourcingService::migrate_from_v1(old_cards) {
+                    Ok(events) => {
+                        // Persist the data (including the new UUIDs)
+                        storage.store(CARD_EVENTS_KEY, Json(&events.borrow()));
 
-                // Persist the data (including the new UUIDs)
-                storage.store(CARD_EVENTS_KEY, Json(&events.borrow()));
-
-                // Use the converted events
-                events
+                        // Use the converted events
+                        events
+                    }
+                    Err(error) => {
+                        // Start without cards, keeping the old data instead of storing over it
+                        load_error = Some(error);
+                        EventSourcingService::new()
+                    }
+                }
             } else {
                 // If no such entry exists, create a new one
                 EventSourcingService::new()
             }
         };
 
-        // Load the arena from localStorage
-        let arena = {
-            if let Ok(loaded_arena) = storage.restore(ARENA_KEY) {
+        // Load the profile from localStorage
+        let profile = {
+            if let Json(Ok(profile)) = storage.restore(PROFILE_KEY) {
+                profile
+            } else if let Some(arena) = storage
+                .restore::<Text>(ARENA_KEY)
+                .ok()
                 // Remove the quotes & restore the arena from localStorage
-                Arena::from_str(&loaded_arena[1..&loaded_arena.len() - 1]).unwrap()
+                .and_then(|loaded_arena: String| {
+                    Arena::from_str(loaded_arena.trim_matches('"')).ok()
+                })
+            {
+                Profile::new(arena)
             } else {
                 // If no such entry exists, default to the LegendaryArena
-                Arena::LegendaryArena
+                Profile::default()
             }
         };
 
-        // Create a mutable copy of the current projection for sorting
-        let mut cards = events.borrow().get_projection().clone();
+        // Load the settings from localStorage
+        let settings = if let Json(Ok(settings)) = storage.restore(SETTINGS_KEY) {
+            settings
+        } else {
+            // Default to the language of the browser
+            let language = yew::utils::window().navigator().language();
 
-        // Compute the calculated values of all cards
-        CardEntry::compute_all(&mut cards, Some(&arena));
+            Settings {
+                locale: Locale::from_language_tag(&language.unwrap_or_default()),
+                ..Settings::default()
+            }
+        };
 
-        // Sort by remaining time
-        cards.sort_by(CardEntry::sort_by_remaining(Some(&arena)));
+        // Use the colors of the saved theme
+        apply_theme(settings.theme);
 
-        // Compute the in_order values
-        CardEntry::sum_all(&mut cards).unwrap();
+        // Compute, sort and sum a copy of the current projection
+        let (planner, error) =
+            match Planner::new(events.borrow().get_projection().clone(), profile.clone()) {
+                Ok(planner) => (planner, None),
+                Err(error) => (
+                    // Show what's wrong with the saved data and start without cards instead
+                    Planner::new(Vec::new(), profile)
+                        // The default profile has nothing to project, so it can't fail
+                        .or_else(|_| Planner::new(Vec::new(), Profile::default()))
+                        .unwrap(),
+                    Some(settings.locale.messages().error(&error)),
+                ),
+            };
+        let error =
+            error.or_else(|| load_error.map(|error| settings.locale.messages().error(&error)));
 
         // The state of the application
-        let state = State { cards, arena };
+        let state = State {
+            planner,
+            error,
+            selected: HashSet::new(),
+            drafts: DraftStore::new(),
+            focus_new_card: false,
+            trash: events.trash().unwrap_or_default(),
+        };
+
+        // Listen to the changes saved by other tabs
+        let on_storage = link.callback(Msg::StorageChanged);
+        let storage_listener = EventListener::new(&yew::utils::window(), "storage", move |event| {
+            on_storage.emit(event.dyn_ref::<StorageEvent>().and_then(|e| e.key()))
+        });
 
         Self {
             link,
+            props,
             events,
             storage,
             state,
+            settings,
+            new_card_ref: NodeRef::default(),
+            _storage_listener: storage_listener,
         }
     }
 
     fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        let changes_plan = msg.changes_plan();
+
         match msg {
-            Msg::Create(mut card) => {
-                // Make a create event
-                self.events
-                    .borrow_mut()
-                    .push(Event::create(card.clone()))
-                    .unwrap();
+            Msg::Create(card) => {
+                // Add the card to the list (checking its values)
+                let result = self
+                    .state
+                    .planner
+                    .insert(card.clone())
+                    // Make a create event
+                    .and_then(|_| self.events.push(Event::create(card)));
 
-                // Generate the computed values of the card
-                card.computed = card.calc_remaining(Some(&self.state.arena));
+                // Handle the state change
+                self.handle_state_change(result);
+            }
+            Msg::Edit(card) => {
+                // Keep the edit, remembering the saved card it's based on
+                if let Ok(base) = self.state.planner.get(&card.uuid) {
+                    self.state.drafts.edit(base, card);
+                }
+            }
+            Msg::Cancel(uuid) => self.state.drafts.discard(&uuid),
+            Msg::Update(card) => {
+                let uuid = card.uuid;
 
-                // Add the card to the list
-                self.state.cards.push(card);
+                // Replace the outdated card entry (checking its values)
+                let result = self
+                    .state
+                    .planner
+                    .update(card.clone())
+                    // Make an update event
+                    .and_then(|_| self.events.push(Event::update(card)));
+
+                // Keep the draft if it couldn't be saved
+                if result.is_ok() {
+                    self.state.drafts.discard(&uuid);
+                }
 
                 // Handle the state change
-                self.handle_state_change();
+                self.handle_state_change(result);
             }
-            Msg::Update(index, mut card) => {
-                // Make an update event
-                self.events
-                    .borrow_mut()
-                    .push(Event::update(card.clone()))
-                    .unwrap();
-
-                // Generate the computed values of the card
-                card.computed = card.calc_remaining(Some(&self.state.arena));
+            Msg::Delete(card) => {
+                // Remove the card
+                let result = self
+                    .state
+                    .planner
+                    .remove(&card.uuid)
+                    // Make a delete event
+                    .and_then(|_| self.events.push(Event::delete(card)));
 
-                // Replace the outdated card entry
-                self.state.cards[index] = card;
+                // Move the focus away from the removed row
+                self.state.focus_new_card = result.is_ok();
 
                 // Handle the state change
-                self.handle_state_change();
+                self.handle_state_change(result);
             }
-            Msg::Delete(index, card) => {
-                // Make a create event
-                self.events.borrow_mut().push(Event::delete(card)).unwrap();
+            Msg::Restore(card) => {
+                // Add the deleted card again (checking its values)
+                let result = self
+                    .state
+                    .planner
+                    .insert(card.clone())
+                    // Make a create event (with the same UUID)
+                    .and_then(|_| self.events.push(Event::create(card)));
 
-                // Remove the card
-                self.state.cards.remove(index);
+                // Handle the state change
+                self.handle_state_change(result);
+            }
+            Msg::Upgrade(uuid) => {
+                // Raise the level using up the needed cards (checking that there are enough)
+                let result = self.upgrade_card(uuid);
 
                 // Handle the state change
-                self.handle_state_change();
+                self.handle_state_change(result);
             }
             Msg::SetArena(arena) => {
-                // Persist the data
-                self.state.arena = arena;
-                self.storage.store(ARENA_KEY, Json(&self.state.arena));
+                let mut profile = self.state.planner.profile().clone();
+                profile.set_arena(arena);
+
+                // Re-compute all cards for the new arena
+                self.handle_profile_change(profile);
+            }
+            Msg::SetTrophies(trophies) => {
+                let mut profile = self.state.planner.profile().clone();
+                profile.set_trophies(trophies);
+
+                // Re-compute all cards for the inferred arena
+                self.handle_profile_change(profile);
+            }
+            Msg::SetIncome(income) => {
+                let mut profile = self.state.planner.profile().clone();
+                profile.income = income;
+
+                // Re-compute all cards for the new sources
+                self.handle_profile_change(profile);
+            }
+            Msg::SetProgression(progression) => {
+                let mut profile = self.state.planner.profile().clone();
+                profile.progression = progression;
+
+                // Re-compute all cards for the new progression
+                self.handle_profile_change(profile);
+            }
+            Msg::SetKing(king) => {
+                let mut profile = self.state.planner.profile().clone();
+                profile.king = king;
+
+                // Persist the new King level
+                self.handle_profile_change(profile);
+            }
+            Msg::LogTransaction(transaction) => {
+                // Change the amount of cards and record where they came from
+                let result = self.log_transaction(transaction);
+                let logged = result.is_ok();
+
+                // Handle the state change
+                self.handle_state_change(result);
+
+                if logged {
+                    let mut profile = self.state.planner.profile().clone();
+                    profile.fill_rate = FillRate::from_transactions(
+                        self.events.borrow_transactions().get_projection(),
+                    );
+
+                    // Re-compute all cards using the calibrated fill rates
+                    self.handle_profile_change(profile);
+                }
+            }
+            Msg::SetView(view) => {
+                self.settings.view = view;
+
+                // Persist the settings
+                self.storage.store(SETTINGS_KEY, Json(&self.settings));
+            }
+            Msg::SortBy(column) => {
+                self.settings.view.toggle_sort(column);
+
+                // Persist the settings
+                self.storage.store(SETTINGS_KEY, Json(&self.settings));
+            }
+            Msg::BulkEnter(cards) => {
+                // Add or update all entered cards (or none of them)
+                let result = self.change_all(|listing| listing.enter_cards(cards));
+
+                // Handle the state change
+                self.handle_state_change(result);
+            }
+            Msg::Bulk(action) => {
+                let deleting = action == BulkAction::Delete;
+
+                // Change all selected cards (or none of them)
+                let result = self.change_all(|listing| listing.apply_bulk_action(action));
+
+                // Move the focus away from the removed rows
+                self.state.focus_new_card = deleting && result.is_ok();
+
+                // Handle the state change
+                self.handle_state_change(result);
+            }
+            Msg::ToggleSelected(uuid) => {
+                if !self.state.selected.remove(&uuid) {
+                    self.state.selected.insert(uuid);
+                }
+            }
+            Msg::SelectListed(select) => {
+                let listed = self.settings.view.apply(self.state.planner.cards());
 
-                // Compute the calculated values of all cards
-                CardEntry::compute_all(&mut self.state.cards, Some(&self.state.arena));
+                for card in listed {
+                    if select {
+                        self.state.selected.insert(card.uuid);
+                    } else {
+                        self.state.selected.remove(&card.uuid);
+                    }
+                }
+            }
+            Msg::SetSync(sync) => {
+                self.settings.sync = sync;
+
+                // Persist the settings
+                self.storage.store(SETTINGS_KEY, Json(&self.settings));
+            }
+            Msg::SetLocale(locale) => {
+                self.settings.locale = locale;
 
-                // Sort by remaining time
-                self.state
-                    .cards
-                    .sort_by(CardEntry::sort_by_remaining(Some(&self.state.arena)));
+                // Persist the settings
+                self.storage.store(SETTINGS_KEY, Json(&self.settings));
+            }
+            Msg::SetTheme(theme) => {
+                self.settings.theme = theme;
+                apply_theme(theme);
 
-                // Compute the in_order values
-                CardEntry::sum_all(&mut self.state.cards).unwrap();
+                // Persist the settings
+                self.storage.store(SETTINGS_KEY, Json(&self.settings));
             }
+            Msg::SetLayout(layout) => {
+                self.settings.layout = layout;
+
+                // Persist the settings
+                self.storage.store(SETTINGS_KEY, Json(&self.settings));
+            }
+            Msg::ExportCalendar => {
+                // Only generate the file when it's wanted
+                if let Err(error) = self.export_calendar() {
+                    let m = self.messages();
+                    self.state.error = Some(fill(m.cannot_export, &[&m.error(&error)]));
+                }
+            }
+            Msg::Pulled(log) => {
+                // Save the events received from the server
+                self.handle_remote_log(log, |report| report.received > 0)
+            }
+            Msg::StorageChanged(key) => match key.as_deref() {
+                Some(CARD_EVENTS_KEY) | Some(TRANSACTION_EVENTS_KEY) | None => {
+                    // Merge the event logs of the other tab
+                    self.handle_remote_events()
+                }
+                Some(PROFILE_KEY) => {
+                    if let Json(Ok(profile)) = self.storage.restore(PROFILE_KEY) {
+                        // Re-compute all cards for the other tab's profile
+                        self.state.error = self
+                            .state
+                            .planner
+                            .set_profile(profile)
+                            .err()
+                            .map(|e| self.messages().error(&e));
+                    }
+                }
+                Some(SETTINGS_KEY) => {
+                    if let Json(Ok(settings)) = self.storage.restore(SETTINGS_KEY) {
+                        self.settings = settings;
+                        apply_theme(self.settings.theme);
+                    }
+                }
+                Some(_) => return false,
+            },
+        }
+
+        // Share the new plan (copying it only if it may have changed)
+        if changes_plan {
+            self.emit_plan();
         }
 
         // Re-render
         true
     }
 
-    fn change(&mut self, _: Self::Properties) -> ShouldRender {
-        true
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        // Only the callback can change, which isn't rendered
+        self.props = props;
+        false
+    }
+
+    fn rendered(&mut self, first_render: bool) {
+        // Share the loaded plan
+        if first_render {
+            self.emit_plan();
+        }
+
+        if self.state.focus_new_card {
+            self.state.focus_new_card = false;
+
+            if let Some(input) = self.new_card_ref.cast::<HtmlElement>() {
+                let _ = input.focus();
+            }
+        }
     }
 
     fn view(&self) -> Html {
-        let total_gold = gold_string(
-            self.state
-                .cards
-                .iter()
-                .fold(0, |acc, card| acc + card.get_needed_gold()),
-        );
+        let locale = self.settings.locale;
 
         html! {
             <>
 
-            <div style=BOTTOM_PADDING>
-                { "Selected arena: " }
-                <select onchange=self.link.callback(|event: ChangeData| {
-                    if let yew::events::ChangeData::Select(data) = event {
-                        Msg::SetArena(Arena::from_str(&data.value()).unwrap())
-                    } else {
-                        panic!("Big oof");
-                    }
-                }) >
-                    { self.get_arenas() }
-                </select>
-            </div>
+            // The settings of the player
+            { self.view_profile() }
 
-            <div style=GRID>
+            { self.view_error() }
 
-                // Render all cards
-                {
-                    for self.state.cards.iter().enumerate().map(|(i, card)| html!{
-                        <CardInfo
-                            card=card.clone()
-                            on_update=self.link.callback(move |c: CardEntry| Msg::Update(i, c))
-                            on_delete=self.link.callback(move |c: CardEntry| Msg::Delete(i, c),)
+            // Search and filter the cards
+            <ListViewControls
+                view=self.settings.view.clone()
+                on_change=self.link.callback(Msg::SetView)
+                locale=locale
+            />
+
+            // Choose how to show the cards
+            { self.view_layouts() }
+
+            // Change the selected cards
+            <BulkActions
+                selected=self.state.selected.len()
+                on_action=self.link.callback(Msg::Bulk)
+                locale=locale
+            />
+
+            // Show the cards as a table or as the collection of the game
+            {
+                match self.settings.layout {
+                    Layout::Table => self.view_table(),
+                    Layout::Collection => html! {
+                        <CollectionGrid
+                            cards=self.settings.view.apply(self.state.planner.cards()).into_iter().cloned().collect::<Vec<_>>()
+                            king_level=self.state.planner.profile().king.level
+                            on_upgrade=self.link.callback(Msg::Upgrade)
+                            locale=locale
                         />
-                    })
+                    },
                 }
+            }
 
-                // Show a field for card input
-                <CardInput
-                    on_create=self.link.callback(|card: CardEntry| Msg::Create(card))
-                    total_gold=total_gold
-                />
+           // Restore deleted cards
+           <Trash
+                cards=self.state.trash.clone()
+                on_restore=self.link.callback(Msg::Restore)
+                locale=locale
+           />
+
+           // Add many cards at once
+           <BulkInput on_enter=self.link.callback(Msg::BulkEnter) locale=locale />
+
+           // Log cards received and donated
+           <CardTransactions
+                cards=self.state.planner.cards().clone()
+                arena=self.current_arena().clone()
+                transactions=self.events.borrow_transactions().get_projection().clone()
+                on_log=self.link.callback(Msg::LogTransaction)
+                locale=locale
+           />
+
+           // Plot the planned upgrades
+           <ProgressCharts cards=self.state.planner.cards().clone() locale=locale />
 
-           </div>
+           // Offer the planned upgrades as a calendar
+           { self.view_export() }
+
+           // Sync the event logs with other devices
+           <SyncPanel
+                settings=self.settings.sync.clone()
+                log=SyncLog::from_events(&self.events)
+                on_change=self.link.callback(Msg::SetSync)
+                on_pull=self.link.callback(Msg::Pulled)
+                locale=locale
+           />
+
+           // Compare the plan to other scenarios
+           <ScenarioComparison
+                cards=self.state.planner.cards().clone()
+                profile=self.state.planner.profile().clone()
+                locale=locale
+           />
 
            </>
         }
     }
 }
 
-const GRID: &str = "
-    display: grid;
-    grid-template-columns: auto 4em 4em repeat(10, auto);
-    gap: 5px;
-";
+impl CardsListing {
+    fn emit_plan(&self) {
+        self.props.on_plan.emit(Plan {
+            cards: self.state.planner.cards().clone(),
+            profile: self.state.planner.profile().clone(),
+            history: self.events.borrow().clone(),
+            locale: self.settings.locale,
+        });
+    }
 
-const BOTTOM_PADDING: &str = "
-    padding-bottom: 1em;
-";
+    fn handle_state_change(&mut self, result: Result<()>) {
+        // Show what went wrong instead of persisting
+        if let Err(error) = result {
+            // The planner may hold a change the event log refused, so start over from the log
+            self.rebuild_planner();
+            self.forget_removed_cards();
 
-impl CardsListing {
-    fn handle_state_change(&mut self) {
-        // Sort by remaining time
-        self.state
-            .cards
-            .sort_by(CardEntry::sort_by_remaining(Some(&self.state.arena)));
+            self.state.error = Some(self.messages().error(&error));
+            return;
+        }
 
-        // Compute the in_order values
-        CardEntry::sum_all(&mut self.state.cards).unwrap();
+        self.forget_removed_cards();
+        self.state.error = None;
 
         // Persist the data
         self.storage
             .store(CARD_EVENTS_KEY, Json(&self.events.borrow()));
+        self.storage.store(
+            TRANSACTION_EVENTS_KEY,
+            Json(&self.events.borrow_transactions()),
+        );
+    }
+
+    /// Computes, sorts and sums the cards of the event log again
+    fn rebuild_planner(&mut self) {
+        let cards = self.events.borrow().get_projection().clone();
+
+        // Logged cards were valid for the profile, so this only fails if something else is wrong
+        if let Ok(planner) = Planner::new(cards, self.state.planner.profile().clone()) {
+            self.state.planner = planner;
+        }
+    }
+
+    /// Forgets the selection and drafts of removed cards, moving them to the trash
+    fn forget_removed_cards(&mut self) {
+        self.state.trash = self.events.trash().unwrap_or_default();
+
+        let cards = self.state.planner.cards();
+        self.state
+            .selected
+            .retain(|uuid| cards.iter().any(|card| card.uuid == *uuid));
+        self.state.drafts.retain_cards(cards);
+    }
+
+    /**
+    Merges the event logs saved by another tab into the local ones
+
+    The merged logs only get saved if the other tab lacks some local events,
+    so the tabs don't keep answering each other's changes.
+    */
+    fn handle_remote_events(&mut self) {
+        let mut log = SyncLog::new();
+
+        if let Json(Ok(cards)) = self.storage.restore(CARD_EVENTS_KEY) {
+            log.cards = cards;
+        }
+        if let Json(Ok(transactions)) = self.storage.restore(TRANSACTION_EVENTS_KEY) {
+            log.transactions = transactions;
+        }
+
+        self.handle_remote_log(log, MergeReport::is_ahead);
+    }
+
+    /// Merges event logs from elsewhere into the local ones, saving them if `persist` says so
+    fn handle_remote_log(&mut self, log: SyncLog, persist: impl Fn(&MergeReport) -> bool) {
+        let report = match log.merge_into(&mut self.events) {
+            Ok(report) => report,
+            Err(error) => {
+                self.state.error = Some(self.messages().error(&error));
+                return;
+            }
+        };
+
+        // Re-compute all cards from the merged log (keeping the drafts to detect their conflicts)
+        let result = Planner::new(
+            self.events.borrow().get_projection().clone(),
+            self.state.planner.profile().clone(),
+        )
+        .map(|planner| self.state.planner = planner);
+
+        if result.is_err() || persist(&report) {
+            self.handle_state_change(result);
+        } else {
+            self.forget_removed_cards();
+            self.state.error = None;
+        }
+
+        // Tell the user about the cards changed in both tabs
+        if !report.conflicts.is_empty() {
+            let names = report
+                .conflicts
+                .iter()
+                .filter_map(|uuid| {
+                    self.events
+                        .borrow()
+                        .get_projection()
+                        .iter()
+                        .find(|card| card.uuid == *uuid)
+                        .map(|card| card.name.clone())
+                })
+                .collect::<Vec<_>>();
+
+            self.state.error = Some(fill(
+                self.messages().tab_conflicts,
+                &[&report.conflicts.len(), &names.join(", ")],
+            ));
+        }
+    }
+
+    /// Applies a transaction to its card, recording both in the event logs (or neither)
+    fn log_transaction(&mut self, transaction: CardTransaction) -> Result<()> {
+        self.change_all(|listing| {
+            let card = transaction.apply(listing.state.planner.get(&transaction.card)?)?;
+
+            listing.update_card(card)?;
+            listing.events.push_transaction(transaction)
+        })
+    }
+
+    /// Replaces the card with the same UUID, recording an update event
+    fn update_card(&mut self, card: CardEntry) -> Result<()> {
+        self.state.planner.update(card.clone())?;
+        self.events.push(Event::update(card))
+    }
+
+    /// Upgrades the card with a given UUID to the next level, recording an update event
+    fn upgrade_card(&mut self, uuid: Uuid) -> Result<()> {
+        let king_level = self.state.planner.profile().king.level;
+        let card = self.state.planner.get(&uuid)?.upgraded(king_level)?;
+
+        self.update_card(card)
+    }
+
+    /// Removes the card with a given UUID, recording a delete event
+    fn delete_card(&mut self, uuid: Uuid) -> Result<()> {
+        let card = self.state.planner.remove(&uuid)?;
+        self.events.push(Event::delete(card))
+    }
+
+    /**
+    Makes several changes of the cards, keeping none of them if any fails
+
+    Only the event logs are restored, as `handle_state_change` rebuilds the planner from them.
+    */
+    fn change_all(&mut self, change: impl FnOnce(&mut Self) -> Result<()>) -> Result<()> {
+        let events = self.events.clone();
+
+        let result = change(self);
+        if result.is_err() {
+            self.events = events;
+        }
+
+        result
+    }
+
+    /// Updates the cards with the same names as the entered ones, adding the others
+    fn enter_cards(&mut self, cards: Vec<BulkCard>) -> Result<()> {
+        // The changed cards and whether they exist already
+        let mut changes: Vec<(bool, CardEntry)> = Vec::new();
+
+        // Find all changed cards first, merging lines which enter the same card
+        for entered in cards {
+            let name = entered.name.to_lowercase();
+            let earlier = changes
+                .iter()
+                .position(|(_, card)| card.name.to_lowercase() == name);
+
+            let (exists, card) = match earlier {
+                // Lines entering the same card again change the earlier result
+                Some(i) => {
+                    let (exists, card) = changes.remove(i);
+                    (exists, entered.apply_to(&card))
+                }
+                None => match self
+                    .state
+                    .planner
+                    .cards()
+                    .iter()
+                    .find(|card| card.name.to_lowercase() == name)
+                {
+                    Some(card) => (true, entered.apply_to(card)),
+                    None => (false, entered.to_card()),
+                },
+            };
+
+            changes.push((exists, card));
+        }
+
+        for (exists, card) in changes {
+            if exists {
+                self.update_card(card)?;
+            } else {
+                self.state.planner.insert(card.clone())?;
+                self.events.push(Event::create(card))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Changes all selected cards (in the planned order)
+    fn apply_bulk_action(&mut self, action: BulkAction) -> Result<()> {
+        let selected = self
+            .state
+            .planner
+            .cards()
+            .iter()
+            .filter(|card| self.state.selected.contains(&card.uuid))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        match action {
+            BulkAction::SetLevel(level) => {
+                for mut card in selected {
+                    card.level = level;
+                    self.update_card(card)?;
+                }
+            }
+            BulkAction::SetDeck(in_deck) => {
+                for mut card in selected {
+                    card.in_deck = in_deck;
+                    self.update_card(card)?;
+                }
+            }
+            BulkAction::Delete => {
+                for card in selected {
+                    self.delete_card(card.uuid)?;
+                }
+
+                self.state.selected.clear();
+            }
+            BulkAction::ClearSelection => self.state.selected.clear(),
+        }
+
+        Ok(())
+    }
+
+    fn handle_profile_change(&mut self, profile: Profile) {
+        // Re-compute all cards for the new profile (keeping the old one if it doesn't work)
+        match self.state.planner.set_profile(profile.clone()) {
+            Ok(()) => {
+                self.state.error = None;
+
+                // Persist the data
+                self.storage.store(PROFILE_KEY, Json(&profile));
+            }
+            Err(error) => self.state.error = Some(self.messages().error(&error)),
+        }
+    }
+
+    /// Returns the arena the player is in today (which may be a milestone reached meanwhile)
+    fn current_arena(&self) -> &Arena {
+        self.state
+            .planner
+            .profile()
+            .current_arena(Local::now().date_naive())
+    }
+
+    fn messages(&self) -> &'static Messages {
+        self.settings.locale.messages()
+    }
+
+    /// Downloads the planned upgrades as an iCalendar file
+    fn export_calendar(&self) -> Result<()> {
+        let locale = self.settings.locale;
+        let m = locale.messages();
+
+        let calendar = to_ical(self.state.planner.cards(), |card, data, date| {
+            Ok((
+                fill(m.upgrade_card, &[&card.name, &(card.level + 1)]),
+                fill(
+                    m.calendar_description,
+                    &[
+                        &locale.format_integer(card.get_needed_cards()?),
+                        &locale.format_integer(data.cards_remaining),
+                        &locale.format_integer(card.get_needed_gold()?),
+                        &locale.format_date(date),
+                    ],
+                ),
+            ))
+        })?;
+
+        download("cr-tools.ics", &data_url("text/calendar", &calendar));
+        Ok(())
+    }
+
+    /// Renders a button downloading the planned upgrades as an iCalendar file
+    fn view_export(&self) -> Html {
+        html! {
+            <div class="section">
+                <button onclick=self.link.callback(|_| Msg::ExportCalendar)>
+                    { self.messages().export_calendar }
+                </button>
+            </div>
+        }
+    }
+
+    /// Renders the headers of the table's columns
+    fn view_table(&self) -> Html {
+        let locale = self.settings.locale;
+        let total_gold =
+            locale.format_gold(self.state.planner.cards().iter().fold(0, |acc, card| {
+                acc + card.get_needed_gold().unwrap_or_default()
+            }));
+
+        html! {
+                <table class="cards" aria-label=self.messages().cards>
+
+                    // The column headers (some of which sort the cards)
+                    <thead>{ self.view_header() }</thead>
+
+                    // Render the listed cards (keyed, so edits and focus stay with their cards)
+                    <tbody>
+                    {
+                        for self.settings.view.apply(self.state.planner.cards()).into_iter().map(|card| {
+                            let uuid = card.uuid;
+
+                            html!{
+                                <CardInfo
+                                    key=uuid.to_string()
+                                    card=card.clone()
+                                    draft=self.state.drafts.get(&uuid).map(|draft| draft.card.clone())
+                                    conflict=self.state.drafts.has_conflict(card)
+                                    level_cap=self.level_cap(card)
+                                    king_level=self.state.planner.profile().king.level
+                                    selected=self.state.selected.contains(&uuid)
+                                    on_edit=self.link.callback(Msg::Edit)
+                                    on_cancel=self.link.callback(move |_| Msg::Cancel(uuid))
+                                    on_update=self.link.callback(Msg::Update)
+                                    on_delete=self.link.callback(Msg::Delete)
+                                    on_upgrade=self.link.callback(Msg::Upgrade)
+                                    on_select=self.link.callback(move |_| Msg::ToggleSelected(uuid))
+                                    locale=locale
+                                />
+                            }
+                        })
+                    }
+                    </tbody>
+
+                    // Show a field for card input
+                    <tfoot>
+                        <CardInput
+                            on_create=self.link.callback(|card: CardEntry| Msg::Create(card))
+                            total_gold=total_gold
+                            name_ref=self.new_card_ref.clone()
+                            locale=locale
+                        />
+                    </tfoot>
+
+                </table>
+        }
     }
 
-    fn get_arenas(&self) -> Html {
-        Arena::iter()
-            .map(|arena| {
-                let name = format!("{:?}", arena);
+    fn view_layouts(&self) -> Html {
+        let m = self.messages();
+
+        html! {
+            <div class="section" role="group" aria-label=m.layout>
+            {
+                for Layout::iter().map(|layout| html! {
+                    <button
+                        aria-pressed=(self.settings.layout == layout).to_string()
+                        onclick=self.link.callback(move |_| Msg::SetLayout(layout))
+                    >
+                        { m.layout(&layout) }
+                    </button>
+                })
+            }
+            </div>
+        }
+    }
+
+    fn view_header(&self) -> Html {
+        let sortable = |label: &str, column: SortColumn| {
+            let view = &self.settings.view;
+
+            // Show the direction of the sorted column
+            let (arrow, sort) = match (view.sort == column, view.descending) {
+                (true, false) => (" ▲", "ascending"),
+                (true, true) => (" ▼", "descending"),
+                (false, _) => ("", "none"),
+            };
+
+            html! {
+                <th scope="col" aria-sort=sort>
+                    <button onclick=self.link.callback(move |_| Msg::SortBy(column.clone()))>
+                        { label }
+                        <span aria-hidden="true">{ arrow }</span>
+                    </button>
+                </th>
+            }
+        };
+        let plain = |label: &str| html! { <th scope="col" class="unsorted">{ label }</th> };
+
+        // Check the box if all listed cards are selected
+        let listed = self.settings.view.apply(self.state.planner.cards());
+        let m = self.messages();
+        let all_selected = !listed.is_empty()
+            && listed
+                .iter()
+                .all(|card| self.state.selected.contains(&card.uuid));
+
+        html! {
+            <tr>
+                <th scope="col">
+                    <input
+                        type="checkbox"
+                        aria-label=m.select_listed
+                        checked=all_selected
+                        onclick=self.link.callback(move |_| Msg::SelectListed(!all_selected))
+                    />
+                </th>
+                { sortable(m.name, SortColumn::Name) }
+                { sortable(m.level, SortColumn::Level) }
+                { plain(m.have) }
+                { sortable(m.rarity, SortColumn::Rarity) }
+                { plain(m.need) }
+                { plain(m.remaining) }
+                { sortable(m.upgrade_progress, SortColumn::Progress) }
+                { plain(m.requests) }
+                { plain(m.weeks) }
+                { plain(m.days) }
+                { sortable(m.days_in_order, SortColumn::Days) }
+                { plain(m.done_on) }
+                { sortable(m.planned_order, SortColumn::Planned) }
+                { sortable(m.gold, SortColumn::Gold) }
+                { plain(m.actions) }
+            </tr>
+        }
+    }
+
+    /// Renders the settings of the player's profile
+    fn view_profile(&self) -> Html {
+        let locale = self.settings.locale;
+        let m = locale.messages();
+        let king_levels = self.state.planner.king_levels();
+
+        html! {
+            <>
+
+            <div class="section">
+                <label>
+                    { m.language }
+                    <select onchange=self.link.callback(|event: ChangeData| {
+                        if let yew::events::ChangeData::Select(data) = event {
+                            Msg::SetLocale(Locale::from_str(&data.value()).unwrap())
+                        } else {
+                            panic!("Big oof");
+                        }
+                    }) >
+                        { self.get_locales() }
+                    </select>
+                </label>
+
+                <label>
+                    { m.theme }
+                    <select onchange=self.link.callback(|event: ChangeData| {
+                        if let yew::events::ChangeData::Select(data) = event {
+                            Msg::SetTheme(Theme::from_str(&data.value()).unwrap())
+                        } else {
+                            panic!("Big oof");
+                        }
+                    }) >
+                        { self.get_themes() }
+                    </select>
+                </label>
+            </div>
+
+            <div class="section">
+                { m.selected_arena }
+                { arena_select(self.current_arena(), self.settings.locale, self.link.callback(Msg::SetArena)) }
+
+                { m.or_trophies }
+                <input
+                    type="number"
+                    placeholder=m.trophies
+                    aria-label=m.trophies
+                    value={self.state.planner.profile().trophies.map(|t| t.to_string()).unwrap_or_default()}
+                    oninput=self.link.batch_callback(|i: InputData| {
+                        i.value.parse::<usize>().ok().map(Msg::SetTrophies).into_iter().collect()
+                    })
+                />
+
+                <br />
+                { self.view_sizes() }
+            </div>
+
+            <div class="section">
+                { m.other_sources }
+                { self.view_income_source(m.chests, |income| &mut income.chests) }
+                { self.view_income_source(m.shop, |income| &mut income.shop) }
+                { self.view_income_source(m.pass_royale, |income| &mut income.pass_royale) }
+                { self.view_income_source(m.challenges, |income| &mut income.challenges) }
+            </div>
 
-                // Skip the training camp
-                if arena == Arena::TrainingCamp {
-                    return html! {};
+            <ArenaProgression
+                progression=self.state.planner.profile().progression.clone()
+                on_change=self.link.callback(Msg::SetProgression)
+                locale=locale
+            />
+
+            <KingTower
+                king=self.state.planner.profile().king.clone()
+                projection=king_levels.as_ref().map_or_else(|_| Vec::new(), |levels| levels.to_vec())
+                on_change=self.link.callback(Msg::SetKing)
+                locale=locale
+            />
+            {
+                match king_levels {
+                    Ok(_) => html! {},
+                    Err(error) => html! { <p role="alert"><em>{ m.error(&error) }</em></p> },
+                }
+            }
+
+            </>
+        }
+    }
+
+    /// Renders the request and donation sizes of the current arena
+    fn view_sizes(&self) -> Html {
+        let arena = self.current_arena();
+        let request = get_request_size(arena);
+        let donation = get_donation_size(arena);
+
+        html! {
+            <small>
+                { fill(self.messages().sizes, &[
+                    &request.common,
+                    &request.rare,
+                    &request.epic,
+                    &donation.common,
+                    &donation.rare,
+                    &get_donation_limit(arena),
+                ]) }
+            </small>
+        }
+    }
+
+    /// Renders a checkbox toggling one of the sources of the income model
+    fn view_income_source(&self, label: &str, source: fn(&mut IncomeModel) -> &mut bool) -> Html {
+        let mut income = self.state.planner.profile().income.clone();
+
+        // Prepare the toggled income model
+        let checked = *source(&mut income);
+        *source(&mut income) = !checked;
+
+        html! {
+            <label>
+                <input
+                    type="checkbox"
+                    checked=checked
+                    onclick=self.link.callback(move |_| Msg::SetIncome(income.clone()))
+                />
+                { label }
+            </label>
+        }
+    }
+
+    /**
+    Returns whether the King level allows upgrading a card, or when it will
+
+    Without a projection of the King levels (whose error `view_profile` shows),
+    capped cards stay capped without a date.
+    */
+    fn level_cap(&self, card: &CardEntry) -> Option<LevelCap> {
+        self.state
+            .planner
+            .level_cap(card)
+            .unwrap_or_else(|_| card.computed.as_ref().map(|data| data.level_cap.clone()))
+    }
+
+    fn view_error(&self) -> Html {
+        if let Some(error) = &self.state.error {
+            html! { <p role="alert"><em>{ self.messages().error } { error }</em></p> }
+        } else {
+            html! {}
+        }
+    }
+
+    fn get_locales(&self) -> Html {
+        Locale::iter()
+            .map(|locale| {
+                html! {
+                    <option
+                        value=format!("{:?}", locale)
+                        selected={self.settings.locale == locale}
+                    >
+                        {locale.name()}
+                    </option>
                 }
+            })
+            .collect::<Html>()
+    }
 
+    fn get_themes(&self) -> Html {
+        Theme::iter()
+            .map(|theme| {
                 html! {
                     <option
-                        value=name
-                        selected={self.state.arena == arena}
+                        value=format!("{:?}", theme)
+                        selected={self.settings.theme == theme}
                     >
-                        {name}
+                        {self.messages().theme(&theme)}
                     </option>
                 }
             })
             .collect::<Html>()
     }
 }
+
+/// Selects the colors of the stylesheet (see `src/css/main.css`)
+fn apply_theme(theme: Theme) {
+    if let Some(root) = yew::utils::document().document_element() {
+        let _ = root.set_attribute("data-theme", theme.attribute());
+    }
+}
@@ -0,0 +1,90 @@
+use super::data_url::data_url;
+use crate::i18n::{fill, Locale};
+use crate::logic::{
+    charts::{gold_curve_svg, timeline_svg, ChartLabels, LONG_GAP_DAYS},
+    types::CardEntry,
+};
+use yew::prelude::*;
+
+/// Plots the planned upgrades over time
+pub struct ProgressCharts {
+    props: Props,
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub cards: Vec<CardEntry>,
+    pub locale: Locale,
+}
+
+impl Component for ProgressCharts {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+        Self { props }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> ShouldRender {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        // Apply the new plan
+        self.props = props;
+
+        // Re-render with the new plan
+        true
+    }
+
+    fn view(&self) -> Html {
+        let locale = self.props.locale;
+        let m = locale.messages();
+
+        html! {
+            <div class="section">
+                <h3>{ m.timeline }</h3>
+                <img
+                    class="chart"
+                    src=data_url("image/svg+xml", &timeline_svg(&self.props.cards, &locale))
+                    alt=m.timeline_description
+                />
+                <br />
+                <small>{ fill(m.long_gaps, &[&LONG_GAP_DAYS]) }</small>
+
+                <h3>{ m.gold_over_time }</h3>
+                {
+                    match gold_curve_svg(&self.props.cards, &locale) {
+                        Ok(svg) => html! {
+                            <img
+                                class="chart"
+                                src=data_url("image/svg+xml", &svg)
+                                alt=m.gold_over_time_description
+                            />
+                        },
+                        Err(error) => html! { <em>{ m.error(&error) }</em> },
+                    }
+                }
+            </div>
+        }
+    }
+}
+
+/// Labels the charts in the language of the user interface
+impl ChartLabels for Locale {
+    fn days(&self, days: f64) -> String {
+        fill(self.messages().chart_days, &[&days])
+    }
+
+    fn gold(&self, gold: usize) -> String {
+        fill(self.messages().chart_gold, &[&self.format_gold(gold)])
+    }
+
+    fn today(&self) -> String {
+        self.messages().chart_today.to_owned()
+    }
+
+    fn in_days(&self, days: f64) -> String {
+        fill(self.messages().chart_in_days, &[&days])
+    }
+}
@@ -0,0 +1,133 @@
+use crate::i18n::{fill, Locale};
+use crate::logic::{
+    catalog::icon_path,
+    types::{CardEntry, Rarity},
+};
+use uuid::Uuid;
+use yew::prelude::*;
+
+/// Shows the cards as tiles with their level and progress, like the collection of the game
+pub struct CollectionGrid {
+    link: ComponentLink<Self>,
+    props: Props,
+}
+
+pub enum Msg {
+    Upgrade(Uuid),
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    /// The listed cards
+    pub cards: Vec<CardEntry>,
+
+    /// The King level of the player, which may not allow upgrading some cards yet
+    pub king_level: usize,
+
+    pub on_upgrade: Callback<Uuid>,
+    pub locale: Locale,
+}
+
+impl Component for CollectionGrid {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self { link, props }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            // Let the listing component upgrade the card
+            Msg::Upgrade(uuid) => self.props.on_upgrade.emit(uuid),
+        }
+
+        // Wait for the new cards
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        // Apply the new cards
+        self.props = props;
+
+        // Re-render with the new cards
+        true
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <ul class="collection section" aria-label=self.props.locale.messages().layouts[1]>
+                { for self.props.cards.iter().map(|card| self.view_tile(card)) }
+            </ul>
+        }
+    }
+}
+
+impl CollectionGrid {
+    fn view_tile(&self, card: &CardEntry) -> Html {
+        let locale = self.props.locale;
+        let m = locale.messages();
+        let needed = card.get_needed_cards().unwrap_or_default();
+
+        // Maxed cards don't need any more cards
+        let progress = if needed == 0 {
+            html! { <small>{ m.maxed }</small> }
+        } else {
+            let description = fill(
+                m.progress,
+                &[
+                    &locale.format_integer(card.have),
+                    &locale.format_integer(needed),
+                ],
+            );
+
+            html! {
+                <>
+                    <progress value=card.have.min(needed) max=needed aria-label=description />
+                    <small aria-hidden="true">
+                        { format!("{}/{}", locale.format_integer(card.have), locale.format_integer(needed)) }
+                    </small>
+                </>
+            }
+        };
+
+        // Highlight the cards which can be upgraded right now
+        let (class, upgrade) = if card.is_ready_to_upgrade(self.props.king_level) {
+            let uuid = card.uuid;
+
+            (
+                format!("tile ready {}", rarity_class(&card.rarity)),
+                html! {
+                    <button
+                        aria-label=fill(m.upgrade_card, &[&card.name, &(card.level + 1)])
+                        onclick=self.link.callback(move |_| Msg::Upgrade(uuid))
+                    >
+                        { m.upgrade }
+                    </button>
+                },
+            )
+        } else {
+            (format!("tile {}", rarity_class(&card.rarity)), html! {})
+        };
+
+        html! {
+            <li class=class>
+                <img src=icon_path(card) alt="" />
+                <strong>{ fill(m.level_of, &[&card.level]) }</strong>
+                <span>{ &card.name }</span>
+                { progress }
+                { upgrade }
+            </li>
+        }
+    }
+}
+
+/// Returns the class coloring an element by a rarity (see `src/css/main.css`)
+pub fn rarity_class(rarity: &Rarity) -> &'static str {
+    match rarity {
+        Rarity::Common => "rarity-common",
+        Rarity::Rare => "rarity-rare",
+        Rarity::Epic => "rarity-epic",
+        Rarity::Legendary => "rarity-legendary",
+    }
+}
@@ -0,0 +1,33 @@
+use wasm_bindgen::JsCast;
+use web_sys::HtmlElement;
+
+/// Embeds text of a given media type (e.g. `text/calendar`) into a data URL
+pub fn data_url(media_type: &str, text: &str) -> String {
+    format!("data:{};charset=utf-8,{}", media_type, percent_encode(text))
+}
+
+/// Downloads a file from a (data) URL, like clicking a link to it
+pub fn download(file_name: &str, url: &str) {
+    let document = yew::utils::document();
+
+    if let Ok(link) = document.create_element("a") {
+        let _ = link.set_attribute("href", url);
+        let _ = link.set_attribute("download", file_name);
+
+        if let Ok(link) = link.dyn_into::<HtmlElement>() {
+            link.click();
+        }
+    }
+}
+
+/// Encodes text for use in a URL
+fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
@@ -0,0 +1,123 @@
+use crate::i18n::{fill, Locale};
+use crate::logic::experience::{KingLevelUp, KingProgress};
+use yew::prelude::*;
+
+/// Shows the King level of the player and when the next ones will be reached
+pub struct KingTower {
+    link: ComponentLink<Self>,
+    props: Props,
+}
+
+pub enum Msg {
+    Level(usize),
+    Experience(usize),
+    DonationXp(usize),
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub king: KingProgress,
+    pub projection: Vec<KingLevelUp>,
+    pub on_change: Callback<KingProgress>,
+    pub locale: Locale,
+}
+
+impl Component for KingTower {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self { link, props }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        let mut king = self.props.king.clone();
+
+        match msg {
+            Msg::Level(level) => king.level = level,
+            Msg::Experience(experience) => king.experience = experience,
+            Msg::DonationXp(xp) => king.donation_xp_per_week = xp,
+        }
+
+        // Give the new progress to the listing component
+        self.props.on_change.emit(king);
+
+        // Wait for the new props
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        // Apply the new progress and projection
+        self.props = props;
+
+        // Re-render with the new state
+        true
+    }
+
+    fn view(&self) -> Html {
+        let m = self.props.locale.messages();
+
+        html! {
+            <div class="section">
+                <label>
+                    { m.king_level }
+                    <input
+                        type="number"
+                        placeholder=m.level.to_lowercase()
+                        value={self.props.king.level}
+                        oninput=self.link.batch_callback(|i: InputData| {
+                            i.value.parse::<usize>().ok().map(Msg::Level).into_iter().collect()
+                        })
+                    />
+                </label>
+
+                { m.experience }
+                <input
+                    type="number"
+                    placeholder=m.experience_placeholder
+                    aria-label=m.experience_placeholder
+                    value={self.props.king.experience}
+                    oninput=self.link.batch_callback(|i: InputData| {
+                        i.value.parse::<usize>().ok().map(Msg::Experience).into_iter().collect()
+                    })
+                />
+
+                { m.donation_xp }
+                <input
+                    type="number"
+                    placeholder=m.donation_xp_placeholder
+                    aria-label=m.donation_xp_placeholder
+                    value={self.props.king.donation_xp_per_week}
+                    oninput=self.link.batch_callback(|i: InputData| {
+                        i.value.parse::<usize>().ok().map(Msg::DonationXp).into_iter().collect()
+                    })
+                />
+
+                <br />
+                { self.view_projection() }
+            </div>
+        }
+    }
+}
+
+impl KingTower {
+    fn view_projection(&self) -> Html {
+        let locale = self.props.locale;
+        let m = locale.messages();
+
+        if let Some(next) = self.props.projection.first() {
+            html! {
+                <small>
+                    { fill(m.next_king_level, &[
+                        &next.level,
+                        &next.card_level_cap,
+                        &locale.format_decimal(next.days, 2),
+                        &locale.format_date(next.date.date_naive()),
+                    ]) }
+                </small>
+            }
+        } else {
+            html! { <small>{ m.no_king_level_up }</small> }
+        }
+    }
+}
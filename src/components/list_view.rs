@@ -0,0 +1,156 @@
+use crate::i18n::Locale;
+use crate::logic::{
+    settings::ListView,
+    types::{CardType, Rarity},
+};
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use yew::prelude::*;
+
+/// Search and filter the listed cards
+pub struct ListViewControls {
+    link: ComponentLink<Self>,
+    props: Props,
+}
+
+pub enum Msg {
+    Search(String),
+    FilterRarity(Option<Rarity>),
+    FilterType(Option<CardType>),
+    ToggleHideMaxed,
+    ToggleDeckOnly,
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub view: ListView,
+    pub on_change: Callback<ListView>,
+    pub locale: Locale,
+}
+
+impl Component for ListViewControls {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self { link, props }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        let mut view = self.props.view.clone();
+
+        match msg {
+            Msg::Search(search) => view.search = search,
+            Msg::FilterRarity(rarity) => view.rarity = rarity,
+            Msg::FilterType(card_type) => view.card_type = card_type,
+            Msg::ToggleHideMaxed => view.hide_maxed = !view.hide_maxed,
+            Msg::ToggleDeckOnly => view.deck_only = !view.deck_only,
+        }
+
+        // Give the new view to the listing component
+        self.props.on_change.emit(view);
+
+        // Wait for the new props
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        // Apply the new view
+        self.props = props;
+
+        // Re-render with the new view
+        true
+    }
+
+    fn view(&self) -> Html {
+        let m = self.props.locale.messages();
+
+        html! {
+            <div class="section">
+                <input
+                    type="search"
+                    placeholder=m.search
+                    aria-label=m.search
+                    value={self.props.view.search.to_owned()}
+                    oninput=self.link.callback(|i: InputData| Msg::Search(i.value))
+                />
+
+                <select aria-label=m.rarity onchange=self.link.callback(|event: ChangeData| {
+                    if let yew::events::ChangeData::Select(data) = event {
+                        Msg::FilterRarity(Rarity::from_str(&data.value()).ok())
+                    } else {
+                        panic!("Big oof");
+                    }
+                }) >
+                    <option value="" selected={self.props.view.rarity.is_none()}>{ m.all_rarities }</option>
+                    { self.get_rarities() }
+                </select>
+
+                <select aria-label=m.card_type onchange=self.link.callback(|event: ChangeData| {
+                    if let yew::events::ChangeData::Select(data) = event {
+                        Msg::FilterType(CardType::from_str(&data.value()).ok())
+                    } else {
+                        panic!("Big oof");
+                    }
+                }) >
+                    <option value="" selected={self.props.view.card_type.is_none()}>{ m.all_types }</option>
+                    { self.get_card_types() }
+                </select>
+
+                <label>
+                    <input
+                        type="checkbox"
+                        checked=self.props.view.hide_maxed
+                        onclick=self.link.callback(|_| Msg::ToggleHideMaxed)
+                    />
+                    { m.hide_maxed }
+                </label>
+
+                <label>
+                    <input
+                        type="checkbox"
+                        checked=self.props.view.deck_only
+                        onclick=self.link.callback(|_| Msg::ToggleDeckOnly)
+                    />
+                    { m.deck_only }
+                </label>
+            </div>
+        }
+    }
+}
+
+impl ListViewControls {
+    fn get_rarities(&self) -> Html {
+        Rarity::iter()
+            .map(|rarity| {
+                let name = format!("{:?}", rarity);
+
+                html! {
+                    <option
+                        value=name
+                        selected={self.props.view.rarity.as_ref() == Some(&rarity)}
+                    >
+                        {self.props.locale.messages().rarity(&rarity)}
+                    </option>
+                }
+            })
+            .collect::<Html>()
+    }
+
+    fn get_card_types(&self) -> Html {
+        CardType::iter()
+            .map(|card_type| {
+                let name = format!("{:?}", card_type);
+
+                html! {
+                    <option
+                        value=name
+                        selected={self.props.view.card_type.as_ref() == Some(&card_type)}
+                    >
+                        {self.props.locale.messages().card_type(&card_type)}
+                    </option>
+                }
+            })
+            .collect::<Html>()
+    }
+}
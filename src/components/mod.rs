@@ -1,4 +1,20 @@
 pub mod app;
+pub mod arena_progression;
+pub mod arena_select;
+pub mod bulk_actions;
+pub mod bulk_input;
 pub mod card_info;
 pub mod card_input;
 pub mod cards_listing;
+pub mod charts;
+pub mod collection;
+pub mod data_url;
+pub mod king_tower;
+pub mod list_view;
+pub mod scenarios;
+pub mod stats;
+pub mod sync;
+pub mod today;
+pub mod transactions;
+pub mod trash;
+pub mod update_notice;
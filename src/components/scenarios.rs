@@ -0,0 +1,269 @@
+use super::arena_select::arena_select;
+use crate::i18n::{fill, Locale, Messages};
+use crate::logic::{
+    error::Result,
+    profile::Profile,
+    scenario::{Scenario, ScenarioResult, UpgradeOrder},
+    types::{Arena, CardEntry},
+};
+use chrono::Local;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+use yew::prelude::*;
+
+/// Compares the current plan to "what-if" scenarios
+pub struct ScenarioComparison {
+    link: ComponentLink<Self>,
+    props: Props,
+    scenarios: Vec<Scenario>,
+
+    /// The computed scenarios (only re-computed if the cards change)
+    results: Vec<Result<ScenarioResult>>,
+
+    name: String,
+    arena: Arena,
+    request_epics: bool,
+    order: UpgradeOrder,
+}
+
+pub enum Msg {
+    Add,
+    Remove(usize),
+    UpdateName(String),
+    UpdateArena(Arena),
+    UpdateOrder(UpgradeOrder),
+    ToggleEpics,
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub cards: Vec<CardEntry>,
+    pub profile: Profile,
+    pub locale: Locale,
+}
+
+impl Component for ScenarioComparison {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            link,
+            arena: props
+                .profile
+                .current_arena(Local::now().date_naive())
+                .clone(),
+            props,
+            scenarios: vec![],
+            results: vec![],
+            name: String::new(),
+            request_epics: true,
+            order: UpgradeOrder::ByRemaining,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::UpdateName(name) => self.name = name,
+            Msg::UpdateArena(arena) => self.arena = arena,
+            Msg::UpdateOrder(order) => self.order = order,
+            Msg::ToggleEpics => self.request_epics = !self.request_epics,
+            Msg::Add => {
+                // Base the scenario on the current profile
+                let mut profile = self.props.profile.clone();
+                profile.set_arena(self.arena.clone());
+
+                if !self.request_epics {
+                    profile.schedule.epic = 0.;
+                }
+
+                let scenario = Scenario {
+                    name: self.name.clone(),
+                    profile,
+                    order: self.order.clone(),
+                };
+
+                // Compute the scenario using copies of the cards
+                self.results.push(scenario.run(&self.props.cards));
+                self.scenarios.push(scenario);
+
+                self.name = String::new();
+            }
+            Msg::Remove(index) => {
+                self.scenarios.remove(index);
+                let _ = self.results.remove(index);
+            }
+        }
+
+        // Re-render
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        let cards_changed = props.cards.len() != self.props.cards.len()
+            || props
+                .cards
+                .iter()
+                .zip(&self.props.cards)
+                .any(|(new, old)| !new.same_values(old));
+
+        // Apply the new plan
+        self.props = props;
+
+        // Only the cards affect the scenarios (which have profiles of their own)
+        if cards_changed {
+            self.results = self
+                .scenarios
+                .iter()
+                .map(|scenario| scenario.run(&self.props.cards))
+                .collect();
+        }
+
+        // Re-render with the new plan
+        true
+    }
+
+    fn view(&self) -> Html {
+        let m = self.messages();
+
+        html! {
+            <div class="section">
+                <h3>{ m.scenarios }</h3>
+
+                // The input fields for new scenarios
+                <input
+                    type="text"
+                    placeholder=m.scenario_name
+                    aria-label=m.scenario_name
+                    value={self.name.to_owned()}
+                    oninput=self.link.callback(|i: InputData| Msg::UpdateName(i.value))
+                />
+
+                { arena_select(&self.arena, self.props.locale, self.link.callback(Msg::UpdateArena)) }
+
+                <select aria-label=m.upgrade_order_label onchange=self.link.callback(|event: ChangeData| {
+                    if let yew::events::ChangeData::Select(data) = event {
+                        Msg::UpdateOrder(UpgradeOrder::from_str(&data.value()).unwrap())
+                    } else {
+                        panic!("Big oof");
+                    }
+                }) >
+                    { self.get_orders() }
+                </select>
+
+                <label>
+                    <input
+                        type="checkbox"
+                        checked=self.request_epics
+                        onclick=self.link.callback(|_| Msg::ToggleEpics)
+                    />
+                    { m.request_epics }
+                </label>
+
+                <button
+                    onclick=self.link.callback(|_| Msg::Add)
+                    disabled={self.name.is_empty()}
+                >
+                    {m.compare}
+                </button>
+
+                { self.view_table(&self.results) }
+            </div>
+        }
+    }
+}
+
+impl ScenarioComparison {
+    fn messages(&self) -> &'static Messages {
+        self.props.locale.messages()
+    }
+
+    /// Renders the cards of the current plan next to the ones of the scenarios
+    fn view_table(&self, results: &[Result<ScenarioResult>]) -> Html {
+        if results.is_empty() {
+            return html! {};
+        }
+
+        let m = self.messages();
+
+        html! {
+            <table>
+                <tr>
+                    <th>{ m.card }</th>
+                    <th>{ m.current_plan }</th>
+                    {
+                        for self.scenarios.iter().zip(results).enumerate().map(|(i, (scenario, result))| html! {
+                            <th>
+                                { &scenario.name } { " " }
+                                <button onclick=self.link.callback(move |_| Msg::Remove(i))> {m.remove} </button>
+                                {
+                                    if let Err(error) = result {
+                                        html! { <><br /><em>{ m.error(error) }</em></> }
+                                    } else {
+                                        html! {}
+                                    }
+                                }
+                            </th>
+                        })
+                    }
+                </tr>
+
+                {
+                    for self.props.cards.iter().filter(|card| card.computed.is_some()).map(|card| html! {
+                        <tr>
+                            <td>{ &card.name }</td>
+                            <td>{ self.get_date(card) }</td>
+                            { for results.iter().map(|result| self.view_cell(card, result)) }
+                        </tr>
+                    })
+                }
+            </table>
+        }
+    }
+
+    /// Renders the result of a scenario for a single card
+    fn view_cell(&self, card: &CardEntry, result: &Result<ScenarioResult>) -> Html {
+        let scenario_card = result.as_ref().ok().and_then(|r| r.find(&card.uuid));
+        let m = self.messages();
+
+        match (scenario_card, result) {
+            (Some(scenario_card), Ok(result)) => html! {
+                <td>
+                    { self.get_date(scenario_card) }
+                    {
+                        result
+                            .days_in_order_delta(card)
+                            .map(|delta| fill(m.days_delta, &[&self.props.locale.format_signed(delta, 1)]))
+                            .unwrap_or_default()
+                    }
+                </td>
+            },
+            _ => html! { <td>{ m.not_available }</td> },
+        }
+    }
+
+    fn get_date(&self, card: &CardEntry) -> String {
+        card.computed
+            .as_ref()
+            .and_then(|data| data.done_in_order_on)
+            .map(|date| self.props.locale.format_date(date.date_naive()))
+            .unwrap_or_else(|| self.messages().not_available.to_owned())
+    }
+
+    fn get_orders(&self) -> Html {
+        UpgradeOrder::iter()
+            .map(|order| {
+                let name = format!("{:?}", order);
+
+                html! {
+                    <option
+                        value=name
+                        selected={self.order == order}
+                    >
+                        {self.messages().upgrade_order(&order)}
+                    </option>
+                }
+            })
+            .collect::<Html>()
+    }
+}
@@ -0,0 +1,150 @@
+use crate::i18n::{fill, Locale};
+use crate::logic::{
+    stats::{trends, CollectionStats, Trend},
+    types::{CardEntry, MAX_LEVEL},
+};
+use libocc::Projector;
+use yew::prelude::*;
+
+/// The amount of weeks to show trends for
+const TREND_WEEKS: usize = 8;
+
+/// Shows aggregated values of the collection and how they changed
+pub struct StatsDashboard {
+    props: Props,
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub cards: Vec<CardEntry>,
+    pub history: Projector<CardEntry>,
+    pub locale: Locale,
+}
+
+impl Component for StatsDashboard {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(props: Self::Properties, _: ComponentLink<Self>) -> Self {
+        Self { props }
+    }
+
+    fn update(&mut self, _msg: Self::Message) -> ShouldRender {
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        // Apply the new collection
+        self.props = props;
+
+        // Re-render with the new collection
+        true
+    }
+
+    fn view(&self) -> Html {
+        let stats = CollectionStats::from_cards(&self.props.cards)
+            .and_then(|stats| Ok((stats, trends(&self.props.history, TREND_WEEKS)?)));
+
+        html! {
+            <div class="section">
+                <h3>{ self.props.locale.messages().statistics }</h3>
+                {
+                    match stats {
+                        Ok((stats, trends)) => html! {
+                            <>
+                                { self.view_summary(&stats) }
+                                { self.view_distribution(&stats) }
+                                { self.view_trends(&trends) }
+                            </>
+                        },
+                        Err(error) => html! { <em>{ self.props.locale.messages().error(&error) }</em> },
+                    }
+                }
+            </div>
+        }
+    }
+}
+
+impl StatsDashboard {
+    fn view_summary(&self, stats: &CollectionStats) -> Html {
+        let locale = self.props.locale;
+        let m = locale.messages();
+
+        html! {
+            <p>
+                { fill(m.cards_with_average, &[
+                    &locale.format_integer(stats.cards),
+                    &self.format_level(stats.average_level),
+                ]) }
+                <br />
+                { fill(m.needed_for_max, &[
+                    &locale.format_integer(stats.cards_to_max),
+                    &locale.format_gold(stats.gold_to_max),
+                    &locale.format_percent(stats.completion, 1),
+                ]) }
+            </p>
+        }
+    }
+
+    /// Formats an average level, if there is one
+    fn format_level(&self, level: Option<f64>) -> String {
+        let locale = self.props.locale;
+
+        level
+            .map(|level| locale.format_decimal(level, 2))
+            .unwrap_or_else(|| locale.messages().not_available.to_owned())
+    }
+
+    /// Renders the amount of cards per level and rarity
+    fn view_distribution(&self, stats: &CollectionStats) -> Html {
+        let m = self.props.locale.messages();
+
+        html! {
+            <table>
+                <tr>
+                    <th>{ m.level }</th>
+                    { for (1..=MAX_LEVEL).map(|level| html! { <th>{ level }</th> }) }
+                </tr>
+                {
+                    for stats.distribution.iter().map(|(rarity, levels)| html! {
+                        <tr>
+                            <td>{ m.rarity(rarity) }</td>
+                            { for levels.iter().map(|count| html! { <td>{ count }</td> }) }
+                        </tr>
+                    })
+                }
+            </table>
+        }
+    }
+
+    /// Renders the weekly history of the collection
+    fn view_trends(&self, trends: &[Trend]) -> Html {
+        let locale = self.props.locale;
+        let m = locale.messages();
+
+        if trends.len() < 2 {
+            return html! { <p><small>{ m.trends_later }</small></p> };
+        }
+
+        html! {
+            <table>
+                <tr>
+                    <th>{ m.week_of }</th>
+                    <th>{ m.average_level }</th>
+                    <th>{ m.complete }</th>
+                    <th>{ m.gold_to_max }</th>
+                </tr>
+                {
+                    for trends.iter().map(|trend| html! {
+                        <tr>
+                            <td>{ locale.format_date(trend.date.date_naive()) }</td>
+                            <td>{ self.format_level(trend.stats.average_level) }</td>
+                            <td>{ locale.format_percent(trend.stats.completion, 1) }</td>
+                            <td>{ locale.format_gold(trend.stats.gold_to_max) }</td>
+                        </tr>
+                    })
+                }
+            </table>
+        }
+    }
+}
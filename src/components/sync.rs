@@ -0,0 +1,195 @@
+use crate::i18n::{fill, Locale};
+use crate::logic::{
+    settings::SyncSettings,
+    sync::{events_url, SyncLog},
+};
+use yew::format::{Json, Text};
+use yew::prelude::*;
+use yew::services::fetch::{FetchService, FetchTask, Request, Response};
+
+/// Push the event logs to a sync server and pull the ones of other devices
+pub struct SyncPanel {
+    link: ComponentLink<Self>,
+    props: Props,
+
+    /// The running request (if any)
+    task: Option<FetchTask>,
+    status: Option<Status>,
+}
+
+/// The outcome of the last sync
+enum Status {
+    Syncing,
+    Synced,
+    Failed(String),
+}
+
+pub enum Msg {
+    SetServer(String),
+    SetProfile(String),
+    Sync,
+    Synced(Result<SyncLog, String>),
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub settings: SyncSettings,
+
+    /// The local event logs to push
+    pub log: SyncLog,
+
+    pub on_change: Callback<SyncSettings>,
+
+    /// Receives the logs of the server (merged with the pushed ones)
+    pub on_pull: Callback<SyncLog>,
+
+    pub locale: Locale,
+}
+
+impl Component for SyncPanel {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            link,
+            props,
+            task: None,
+            status: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::SetServer(server) => {
+                let mut settings = self.props.settings.clone();
+                settings.server = server;
+
+                // Give the new settings to the listing component
+                self.props.on_change.emit(settings);
+            }
+            Msg::SetProfile(profile) => {
+                let mut settings = self.props.settings.clone();
+                settings.profile = profile;
+
+                // Give the new settings to the listing component
+                self.props.on_change.emit(settings);
+            }
+            Msg::Sync => match self.push() {
+                Ok(task) => {
+                    self.task = Some(task);
+                    self.status = Some(Status::Syncing);
+                }
+                Err(error) => self.status = Some(Status::Failed(error)),
+            },
+            Msg::Synced(result) => {
+                self.task = None;
+
+                match result {
+                    Ok(log) => {
+                        self.props.on_pull.emit(log);
+                        self.status = Some(Status::Synced);
+                    }
+                    Err(error) => self.status = Some(Status::Failed(error)),
+                }
+            }
+        }
+
+        // Re-render
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        // Apply the new settings and logs
+        self.props = props;
+
+        // Re-render with the new settings
+        true
+    }
+
+    fn view(&self) -> Html {
+        let settings = &self.props.settings;
+        let ready = !settings.server.trim().is_empty() && !settings.profile.is_empty();
+        let m = self.props.locale.messages();
+
+        html! {
+            <div class="section">
+                <details>
+                    <summary>{ m.sync_title }</summary>
+
+                    <p>
+                        <small>
+                            { m.sync_help }
+                            <code>{ "sync-server" }</code>
+                            { m.sync_help_profiles }
+                        </small>
+                    </p>
+
+                    <input
+                        type="url"
+                        placeholder="http://localhost:8787"
+                        aria-label=m.server
+                        value={settings.server.to_owned()}
+                        oninput=self.link.callback(|i: InputData| Msg::SetServer(i.value))
+                    />
+                    <input
+                        type="text"
+                        placeholder=m.profile
+                        aria-label=m.profile
+                        value={settings.profile.to_owned()}
+                        oninput=self.link.callback(|i: InputData| Msg::SetProfile(i.value))
+                    />
+                    <button
+                        onclick=self.link.callback(|_| Msg::Sync)
+                        disabled={!ready || self.task.is_some()}
+                    >
+                        {m.sync}
+                    </button>
+
+                    {
+                        if let Some(status) = &self.status {
+                            let status = match status {
+                                Status::Syncing => m.syncing.to_owned(),
+                                Status::Synced => m.synced.to_owned(),
+                                Status::Failed(error) => fill(m.cannot_sync, &[error]),
+                            };
+
+                            html! { <p role="status"><em>{ status }</em></p> }
+                        } else {
+                            html! {}
+                        }
+                    }
+                </details>
+            </div>
+        }
+    }
+}
+
+impl SyncPanel {
+    /// Sends the local logs to the server, which answers with the merged ones
+    fn push(&self) -> Result<FetchTask, String> {
+        let settings = &self.props.settings;
+        let url = events_url(&settings.server, &settings.profile)
+            .map_err(|e| self.props.locale.messages().error(&e))?;
+
+        let request = Request::post(url)
+            .header("Content-Type", "application/json")
+            .body(Json(&self.props.log))
+            .map_err(|e| e.to_string())?;
+
+        let callback = self.link.callback(|response: Response<Text>| {
+            let (meta, body) = response.into_parts();
+            let body = body.map_err(|e| e.to_string());
+
+            Msg::Synced(match body {
+                Ok(json) if meta.status.is_success() => {
+                    serde_json::from_str(&json).map_err(|e| e.to_string())
+                }
+                Ok(message) => Err(format!("{} {}", meta.status, message)),
+                Err(error) => Err(error),
+            })
+        });
+
+        FetchService::fetch(request, callback).map_err(|e| e.to_string())
+    }
+}
@@ -0,0 +1,131 @@
+use crate::i18n::{fill, Locale};
+use crate::logic::{
+    profile::Profile,
+    recommendation::{recommend, Recommendation, RequestLog, DAILY_REQUESTS},
+    types::{CardEntry, Rarity},
+};
+use chrono::Local;
+use yew::format::Json;
+use yew::prelude::*;
+use yew::services::storage::{Area, StorageService};
+
+const REQUESTS_KEY: &str = "cr-tools.state.requests";
+
+/// Shows which cards to request today
+pub struct TodayPanel {
+    link: ComponentLink<Self>,
+    props: Props,
+    storage: StorageService,
+    log: RequestLog,
+}
+
+pub enum Msg {
+    Requested(Rarity),
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub cards: Vec<CardEntry>,
+    pub profile: Profile,
+    pub locale: Locale,
+}
+
+impl Component for TodayPanel {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        // Get a reference to localStorage
+        let storage = StorageService::new(Area::Local).expect("Cannot use localStorage");
+
+        // Load the requests made so far
+        let log = if let Json(Ok(log)) = storage.restore(REQUESTS_KEY) {
+            log
+        } else {
+            RequestLog::default()
+        };
+
+        Self {
+            link,
+            props,
+            storage,
+            log,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Requested(rarity) => self.log.record(&rarity, Local::now().date_naive()),
+        }
+
+        // Persist the data
+        self.storage.store(REQUESTS_KEY, Json(&self.log));
+
+        // Re-render
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        // Apply the new plan
+        self.props = props;
+
+        // Re-render with the new plan
+        true
+    }
+
+    fn view(&self) -> Html {
+        let today = Local::now().date_naive();
+        let recommendations = recommend(&self.props.cards, &self.props.profile, &self.log, today);
+        let m = self.props.locale.messages();
+
+        html! {
+            <div class="boxed section">
+                <h3>{ m.today }</h3>
+                { fill(m.requests_made, &[&self.log.made_on(today), &DAILY_REQUESTS]) }
+                { if self.log.epic_made_in_week_of(today) { m.epic_done } else { "" } }
+
+                {
+                    if recommendations.is_empty() {
+                        html! { <p>{ m.nothing_to_request }</p> }
+                    } else {
+                        html! {
+                            <ul>
+                                { for recommendations.iter().map(|r| self.view_recommendation(r)) }
+                            </ul>
+                        }
+                    }
+                }
+            </div>
+        }
+    }
+}
+
+impl TodayPanel {
+    fn view_recommendation(&self, recommendation: &Recommendation) -> Html {
+        let rarity = recommendation.rarity.clone();
+        let locale = self.props.locale;
+        let m = locale.messages();
+
+        html! {
+            <li>
+                <strong>
+                    { fill(m.request_card, &[&recommendation.name, &recommendation.requests]) }
+                </strong>
+                { fill(m.cards_remaining, &[
+                    &m.reason(&recommendation.reason),
+                    &locale.format_integer(recommendation.cards_remaining),
+                ]) }
+                {
+                    recommendation
+                        .done_in_order_on
+                        .map(|date| fill(m.upgrade_on, &[&locale.format_date(date.date_naive())]))
+                        .unwrap_or_default()
+                }
+                { " " }
+                <button onclick=self.link.callback(move |_| Msg::Requested(rarity.clone()))>
+                    { m.requested }
+                </button>
+            </li>
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use crate::i18n::{fill, Locale};
+use crate::logic::{
+    transactions::{stats_by_rarity, CardTransaction, TransactionKind},
+    types::{get_request_size, Arena, CardEntry, Rarity},
+};
+use uuid::Uuid;
+use yew::prelude::*;
+
+/// Log the cards received from requests and donated to others
+pub struct CardTransactions {
+    link: ComponentLink<Self>,
+    props: Props,
+    card: Option<Uuid>,
+    donated: bool,
+    amount: Option<usize>,
+}
+
+pub enum Msg {
+    Log,
+    SelectCard(String),
+    ToggleDonated,
+    UpdateAmount(String),
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub cards: Vec<CardEntry>,
+    pub arena: Arena,
+    pub transactions: Vec<CardTransaction>,
+    pub on_log: Callback<CardTransaction>,
+    pub locale: Locale,
+}
+
+impl Component for CardTransactions {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self {
+            link,
+            props,
+            card: None,
+            donated: false,
+            amount: None,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::SelectCard(uuid) => self.card = Uuid::parse_str(&uuid).ok(),
+            Msg::ToggleDonated => self.donated = !self.donated,
+            Msg::UpdateAmount(amount) => self.amount = amount.parse().ok(),
+            Msg::Log => {
+                let card = self
+                    .props
+                    .cards
+                    .iter()
+                    .find(|card| Some(card.uuid) == self.card);
+
+                if let (Some(card), Some(amount)) = (card, self.amount) {
+                    let kind = if self.donated {
+                        TransactionKind::Donated
+                    } else {
+                        TransactionKind::RequestFilled {
+                            request_size: get_request_size(&self.props.arena).get(&card.rarity),
+                        }
+                    };
+
+                    // Give the new transaction to the listing component
+                    self.props
+                        .on_log
+                        .emit(CardTransaction::new(card, kind, amount));
+
+                    self.amount = None;
+                }
+            }
+        }
+
+        // Re-render
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        // Apply the new cards and transactions
+        self.props = props;
+
+        // Re-render with the new state
+        true
+    }
+
+    fn view(&self) -> Html {
+        let m = self.props.locale.messages();
+
+        html! {
+            <div class="section">
+                { m.log_cards }
+                <select aria-label=m.card onchange=self.link.callback(|event: ChangeData| {
+                    if let yew::events::ChangeData::Select(data) = event {
+                        Msg::SelectCard(data.value())
+                    } else {
+                        panic!("Big oof");
+                    }
+                }) >
+                    <option value="" selected={self.card.is_none()}>{ m.card.to_lowercase() }</option>
+                    { self.get_cards() }
+                </select>
+
+                <input
+                    type="number"
+                    placeholder=m.amount
+                    aria-label=m.amount
+                    value={self.amount.map(|a| a.to_string()).unwrap_or_default()}
+                    oninput=self.link.callback(|i: InputData| Msg::UpdateAmount(i.value))
+                />
+
+                <label>
+                    <input
+                        type="checkbox"
+                        checked=self.donated
+                        onclick=self.link.callback(|_| Msg::ToggleDonated)
+                    />
+                    { m.donated_instead }
+                </label>
+
+                <button
+                    onclick=self.link.callback(|_| Msg::Log)
+                    disabled={self.card.is_none() || self.amount.is_none()}
+                >
+                    {m.log}
+                </button>
+
+                <br />
+                { self.view_stats() }
+            </div>
+        }
+    }
+}
+
+impl CardTransactions {
+    /// Renders the fill rate of the requests and the donations of each rarity
+    fn view_stats(&self) -> Html {
+        let locale = self.props.locale;
+        let m = locale.messages();
+
+        stats_by_rarity(&self.props.transactions)
+            .into_iter()
+            .filter(|(rarity, _)| *rarity != Rarity::Legendary)
+            .map(|(rarity, stats)| {
+                html! {
+                    <small>
+                        { fill(m.transaction_stats, &[
+                            &m.rarity(&rarity),
+                            &stats.requests,
+                            &stats
+                                .fill_rate()
+                                .map(|rate| fill(m.fill_rate, &[&locale.format_percent(rate, 0)]))
+                                .unwrap_or_else(|| m.no_fill_rate.to_owned()),
+                            &locale.format_integer(stats.donated),
+                        ]) }
+                    </small>
+                }
+            })
+            .collect::<Html>()
+    }
+
+    fn get_cards(&self) -> Html {
+        self.props
+            .cards
+            .iter()
+            .map(|card| {
+                html! {
+                    <option
+                        value=card.uuid.to_string()
+                        selected={self.card == Some(card.uuid)}
+                    >
+                        { &card.name }
+                    </option>
+                }
+            })
+            .collect::<Html>()
+    }
+}
@@ -0,0 +1,111 @@
+use crate::i18n::{fill, Locale};
+use crate::logic::{events::DeletedCard, types::CardEntry};
+use chrono::Local;
+use yew::prelude::*;
+
+/// Lists the deleted cards, restoring them on demand
+pub struct Trash {
+    link: ComponentLink<Self>,
+    props: Props,
+}
+
+pub enum Msg {
+    Restore(usize),
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    /// The deleted cards (most recently deleted first)
+    pub cards: Vec<DeletedCard>,
+
+    pub on_restore: Callback<CardEntry>,
+    pub locale: Locale,
+}
+
+impl Component for Trash {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self { link, props }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Restore(i) => {
+                // Give the card to restore to the listing component
+                if let Some(deleted) = self.props.cards.get(i) {
+                    self.props.on_restore.emit(deleted.card.clone());
+                }
+            }
+        }
+
+        // Wait for the new trash
+        false
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        // Apply the new trash
+        self.props = props;
+
+        // Re-render with the new trash
+        true
+    }
+
+    fn view(&self) -> Html {
+        let m = self.props.locale.messages();
+
+        html! {
+            <div class="section">
+                <details>
+                    <summary>{ fill(m.trash, &[&self.props.cards.len()]) }</summary>
+                    {
+                        if self.props.cards.is_empty() {
+                            html! { <p><small>{ m.trash_empty }</small></p> }
+                        } else {
+                            self.view_table()
+                        }
+                    }
+                </details>
+            </div>
+        }
+    }
+}
+
+impl Trash {
+    fn view_table(&self) -> Html {
+        let locale = self.props.locale;
+        let m = locale.messages();
+
+        html! {
+            <table>
+                <thead>
+                    <tr>
+                        <th scope="col">{ m.name }</th>
+                        <th scope="col">{ m.level }</th>
+                        <th scope="col">{ m.rarity }</th>
+                        <th scope="col">{ m.deleted_on }</th>
+                        <th scope="col">{ m.actions }</th>
+                    </tr>
+                </thead>
+                <tbody>
+                {
+                    for self.props.cards.iter().enumerate().map(|(i, deleted)| html! {
+                        <tr>
+                            <td>{ &deleted.card.name }</td>
+                            <td>{ deleted.card.level }</td>
+                            <td>{ m.rarity(&deleted.card.rarity) }</td>
+                            <td>{ locale.format_date(deleted.deleted_on.with_timezone(&Local).date_naive()) }</td>
+                            <td>
+                                <button onclick=self.link.callback(move |_| Msg::Restore(i))>
+                                    { m.restore }
+                                </button>
+                            </td>
+                        </tr>
+                    })
+                }
+                </tbody>
+            </table>
+        }
+    }
+}
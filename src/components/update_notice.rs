@@ -0,0 +1,122 @@
+use crate::constants::meta::VERSION;
+use crate::i18n::{fill, Locale};
+use gloo_events::EventListener;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::MessageEvent;
+use yew::prelude::*;
+
+/// The message asking the service worker (see `static/sw.js`) to look for a newer version
+const CHECK_FOR_UPDATE: &str = "check-for-update";
+
+/// The answer of the service worker when it cached a newer version
+const UPDATE_AVAILABLE: &str = "update-available";
+
+/**
+Makes the app work offline by registering the service worker,
+telling the user when a newer version is ready to be used
+*/
+pub struct UpdateNotice {
+    link: ComponentLink<Self>,
+    props: Props,
+    update_available: bool,
+
+    /// Receives the messages of the service worker (until it's dropped)
+    _listener: Option<EventListener>,
+}
+
+pub enum Msg {
+    UpdateAvailable,
+    Reload,
+    Dismiss,
+}
+
+#[derive(Properties, Clone)]
+pub struct Props {
+    pub locale: Locale,
+}
+
+impl Component for UpdateNotice {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let navigator = yew::utils::window().navigator();
+
+        // Some browsers (or insecure origins) don't support service workers
+        let supported =
+            js_sys::Reflect::has(&navigator, &JsValue::from_str("serviceWorker")).unwrap_or(false);
+
+        let listener = if supported {
+            let container = navigator.service_worker();
+
+            // Listen before asking, so the answer can't be missed
+            let on_update = link.callback(|_| Msg::UpdateAvailable);
+            let listener = EventListener::new(&container, "message", move |event| {
+                let message = event
+                    .dyn_ref::<MessageEvent>()
+                    .and_then(|event| event.data().as_string());
+
+                if message.as_deref() == Some(UPDATE_AVAILABLE) {
+                    on_update.emit(());
+                }
+            });
+
+            // Each version of the app uses its own cache
+            let _ = container.register(&format!("sw.js?version={}", VERSION));
+
+            // Without a controlling worker (on the first visit), the page was just fetched anyway
+            if let Some(worker) = container.controller() {
+                let _ = worker.post_message(&JsValue::from_str(CHECK_FOR_UPDATE));
+            }
+
+            Some(listener)
+        } else {
+            None
+        };
+
+        Self {
+            link,
+            props,
+            update_available: false,
+            _listener: listener,
+        }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::UpdateAvailable => self.update_available = true,
+            Msg::Dismiss => self.update_available = false,
+            Msg::Reload => {
+                // Start the cached newer version
+                let _ = yew::utils::window().location().reload();
+            }
+        }
+
+        // Re-render
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        // Apply the new language
+        self.props = props;
+
+        // Re-render in the new language
+        true
+    }
+
+    fn view(&self) -> Html {
+        if !self.update_available {
+            return html! {};
+        }
+
+        let m = self.props.locale.messages();
+
+        html! {
+            <div class="notice" role="status">
+                { fill(m.update_available, &[&VERSION]) }
+                <button onclick=self.link.callback(|_| Msg::Reload)>{ m.reload }</button>
+                <button onclick=self.link.callback(|_| Msg::Dismiss)>{ m.later }</button>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use super::Messages;
+
+pub const MESSAGES: Messages = Messages {
+    // The app
+    about: "Ein einfacher Rechner für Clash Royale",
+    version: " (Version {})",
+    edit_hint: "Drücke Enter, um eine Änderung zu speichern, oder Escape, um sie zu verwerfen. Gelöschte Karten können aus dem Papierkorb wiederhergestellt werden.",
+    source_code: "Den Quellcode gibt es ",
+    source_code_link: "hier",
+    trademark: "\"Clash Royale\" ist womöglich eine Marke ihres Inhabers, mit dem ich in keinerlei Verbindung stehe.",
+    license: "Die Lizenz: ",
+    language: "Sprache: ",
+    theme: " Farbschema: ",
+    themes: ["Dunkel", "Hell"],
+    update_available: "Eine neuere Version als {} ist verfügbar. ",
+    reload: "Neu laden",
+    later: "Später",
+
+    // Common words
+    not_available: "k. A.",
+    add: "Hinzufügen",
+    remove: "Entfernen",
+    save: "Speichern",
+    overwrite: "Überschreiben",
+    cancel: "Abbrechen",
+    delete: "Löschen",
+    error: "Fehler: ",
+    name: "Name",
+    level: "Level",
+    have: "Vorhanden",
+    rarity: "Seltenheit",
+    gold: "Gold",
+    card: "Karte",
+    amount: "Anzahl",
+    arena: "Arena",
+    date: "Datum",
+    card_type: "Typ",
+    trophies: "Trophäen",
+    profile: "Profil",
+
+    // The cards
+    cards: "Karten",
+    need: "Benötigt",
+    remaining: "Fehlend",
+    requests: "Anfragen",
+    weeks: "Wochen",
+    days: "Tage",
+    days_in_order: "Tage in Reihenfolge",
+    done_on: "Fertig am",
+    select_card: "{} auswählen",
+    select_listed: "Alle angezeigten Karten auswählen",
+    new_card: "Neue Karte",
+    layout: "Ansicht",
+    layouts: ["Tabelle", "Sammlung"],
+    level_of: "Level {}",
+    progress: "{} von {} Karten",
+    maxed: "Max.",
+    upgrade_progress: "Fortschritt",
+    ready_to_upgrade: "Bereit zum Aufwerten",
+    upgrade: "Aufwerten",
+    upgrade_card: "{} auf Level {} aufwerten",
+    actions: "Aktionen",
+    confirm_delete: "{} löschen? Die Karte kann aus dem Papierkorb wiederhergestellt werden. ",
+    confirm_delete_many: "{} Karten löschen? Sie können aus dem Papierkorb wiederhergestellt werden. ",
+    trash: "Papierkorb ({})",
+    trash_empty: "Der Papierkorb ist leer",
+    deleted_on: "Gelöscht am",
+    restore: "Wiederherstellen",
+    total_needed: "Insgesamt benötigt: ",
+    changed_meanwhile: "Zwischenzeitlich geändert",
+    level_cap_until: " (durch das Königslevel begrenzt bis {})",
+    level_cap: " (durch das Königslevel begrenzt)",
+    planned_order: "Geplante Reihenfolge",
+    rarities: ["Gewöhnlich", "Selten", "Episch", "Legendär"],
+    card_types: ["Truppe", "Zauber", "Gebäude"],
+
+    // The profile
+    selected_arena: "Gewählte Arena: ",
+    arenas: [
+        "Trainingslager",
+        "Goblin-Stadion",
+        "Knochengrube",
+        "Barbarenarena",
+        "P.E.K.K.A.s Spielhaus",
+        "Zaubertal",
+        "Baumeisterwerkstatt",
+        "Königsarena",
+        "Frostgipfel",
+        "Dschungelarena",
+        "Schweineberg",
+        "Elektrotal",
+        "Gruselstadt",
+        "Schurkenversteck",
+        "Gipfel der Gelassenheit",
+        "Minenarbeitermine",
+        "Henkersküche",
+        "Königsgruft",
+        "Stilles Heiligtum",
+        "Drachen-Spa",
+        "Legendäre Arena",
+    ],
+    or_trophies: " oder Trophäen: ",
+    sizes: "Anfragegröße: {} gewöhnliche, {} seltene, {} epische - Spendengröße: {} gewöhnliche, {} seltene (bis zu {} pro Tag)",
+    other_sources: "Weitere Kartenquellen: ",
+    chests: "Truhen",
+    shop: "Shop-Angebote",
+    pass_royale: "Pass Royale",
+    challenges: "Herausforderungen",
+    planned_arenas: "Geplante Arenen: ",
+    arena_on: "{} am {} ",
+    king_level: "Königslevel: ",
+    experience: " EP: ",
+    experience_placeholder: "Erfahrung",
+    donation_xp: " EP aus Spenden pro Woche: ",
+    donation_xp_placeholder: "Spenden-EP",
+    next_king_level: "Nächstes Königslevel: {} (Karten bis Level {}) in {} Tagen am {}",
+    no_king_level_up: "Kein Königslevel-Aufstieg geplant",
+
+    // Listing, selecting and entering cards
+    search: "Suche",
+    all_rarities: "Alle Seltenheiten",
+    all_types: "Alle Typen",
+    hide_maxed: "Maximierte Karten ausblenden",
+    deck_only: "Nur das Deck",
+    selected: "{} ausgewählt: ",
+    set_level: "Level setzen",
+    add_to_deck: "Zum Deck hinzufügen",
+    remove_from_deck: "Aus dem Deck entfernen",
+    clear_selection: "Auswahl aufheben",
+    add_many: "Viele Karten hinzufügen",
+    add_many_help: "Eine Karte pro Zeile: Name, Level, Anzahl (optional gefolgt von Seltenheit und Typ auf Englisch, die für die Karten des Spiels bekannt sind), ",
+    add_many_example: "z. B. \"Knight, 12, 340\" oder \"Golem, 9, 20, Epic, Troop\". ",
+    add_many_updates: "Vorhandene Karten mit demselben Namen werden aktualisiert.",
+    add_all: "Alle hinzufügen",
+    tab_conflicts: "{} Karte(n) wurden gleichzeitig in einem anderen Tab geändert, die neuesten Änderungen wurden behalten: {}",
+
+    // Requests and transactions
+    today: "Heute",
+    requests_made: "Heutige Anfragen: {} von {}",
+    epic_done: " (epische Anfrage diese Woche erledigt)",
+    nothing_to_request: "Heute gibt es nichts mehr anzufragen",
+    request_card: "{} anfragen ({}x)",
+    cards_remaining: " - {}; {} Karten fehlen",
+    upgrade_on: ", Aufwertung am {}",
+    requested: "Angefragt",
+    reason_weekly_epic: "Die epische Anfrage dieser Woche ist noch offen",
+    reason_next_in_order: "Als Nächstes in der Aufwertungsreihenfolge",
+    reason_after_upgrade: "{} braucht nach heute keine Anfragen mehr",
+    log_cards: "Karten erfassen: ",
+    donated_instead: "Gespendet (statt aus einer Anfrage erhalten)",
+    log: "Erfassen",
+    transaction_stats: "{}: {} Anfragen erfüllt ({}), {} Karten gespendet; ",
+    fill_rate: "{} Erfüllungsrate",
+    no_fill_rate: "noch keine Erfüllungsrate",
+
+    // Statistics and charts
+    statistics: "Statistiken",
+    cards_with_average: "{} Karten mit einem durchschnittlichen Level von {}",
+    needed_for_max: "Benötigt zum Maximieren: {} Karten und {} Gold ({} erreicht)",
+    trends_later: "Trends erscheinen nach der ersten Woche",
+    week_of: "Woche vom",
+    average_level: "Durchschnittliches Level",
+    complete: "Erreicht",
+    gold_to_max: "Gold bis zum Maximum",
+    timeline: "Zeitleiste",
+    timeline_description: "Der Tag, an dem jede Karte aufgewertet wird",
+    long_gaps: "Hervorgehoben: {} Tage oder mehr ohne Aufwertungen",
+    gold_over_time: "Benötigtes Gold im Zeitverlauf",
+    gold_over_time_description: "Das Gold, das alle Aufwertungen bis zu jedem Tag benötigen",
+    chart_days: "{} Tag(e)",
+    chart_gold: "{} Gold",
+    chart_today: "heute",
+    chart_in_days: "in {} Tagen",
+
+    // Scenarios and exports
+    scenarios: "Was-wäre-wenn-Szenarien",
+    scenario_name: "Name des Szenarios",
+    request_epics: "Epische Karten anfragen",
+    compare: "Vergleichen",
+    current_plan: "Aktueller Plan",
+    days_delta: " ({} Tage)",
+    upgrade_order_label: "Aufwertungsreihenfolge",
+    upgrade_orders: ["Nächstes Level zuerst", "Günstigste zuerst", "Wie aufgelistet"],
+    export_calendar: "Die Aufwertungen in einen Kalender exportieren (.ics)",
+    cannot_export: "Der Kalender kann nicht exportiert werden: {}",
+    calendar_description: "Benötigte Karten: {} ({} fehlen noch)\nBenötigtes Gold: {}\nIn Reihenfolge fertig am: {}",
+
+    // Syncing
+    sync_title: "Mit einem Server synchronisieren",
+    sync_help: "Hält die Karten mehrerer Geräte über einen selbst betriebenen ",
+    sync_help_profiles: " synchron. Geräte mit demselben Profil teilen ihre Karten.",
+    server: "Server-URL",
+    sync: "Synchronisieren",
+    syncing: "Synchronisiere...",
+    synced: "Synchronisiert",
+    cannot_sync: "Synchronisieren fehlgeschlagen: {}",
+
+    // Errors
+    error_invalid_level: "Level {} ist für die Seltenheit {} ungültig",
+    error_date_overflow: "Das vorhergesagte Datum liegt außerhalb des gültigen Bereichs",
+    error_unknown_card: "Keine Karte mit der UUID {}",
+    error_not_enough_cards: "Es können nicht {} von {} vorhandenen Karten abgezogen werden",
+    error_level_capped: "Level {} erfordert ein höheres Königslevel als {}",
+    error_invalid_line: "Zeile {}: {}",
+    error_migration_failed: "Die Daten können nicht übernommen werden: {}",
+    error_event_log_conflict: "Widersprüchliches Ereignis: {}",
+    error_invalid_profile: "Ungültiger Profilname \"{}\" (erlaubt sind bis zu 64 Buchstaben, Ziffern, - und _)",
+    line_value_count: "3 bis 5 Werte erwartet, aber {} gefunden",
+    line_empty_name: "der Name fehlt",
+    line_level_not_a_number: "das Level \"{}\" ist keine Zahl",
+    line_amount_not_a_number: "die Anzahl \"{}\" ist keine Zahl",
+    line_unknown_rarity: "unbekannte Seltenheit \"{}\"",
+    line_unknown_type: "unbekannter Typ \"{}\"",
+};
@@ -0,0 +1,214 @@
+use super::Messages;
+
+pub const MESSAGES: Messages = Messages {
+    // The app
+    about: "A simple calculator for Clash Royale",
+    version: " (version {})",
+    edit_hint: "Press Enter to save an edit or Escape to cancel it. Deleted cards can be restored from the trash.",
+    source_code: "Get the source code ",
+    source_code_link: "here",
+    trademark: "\"Clash Royale\" may be a trademark of its owner, with which I'm not affiliated with at all.",
+    license: "The license: ",
+    language: "Language: ",
+    theme: " Theme: ",
+    themes: ["Dark", "Light"],
+    update_available: "A newer version than {} is available. ",
+    reload: "Reload",
+    later: "Later",
+
+    // Common words
+    not_available: "n/a",
+    add: "Add",
+    remove: "Remove",
+    save: "Save",
+    overwrite: "Overwrite",
+    cancel: "Cancel",
+    delete: "Delete",
+    error: "Error: ",
+    name: "Name",
+    level: "Level",
+    have: "Have",
+    rarity: "Rarity",
+    gold: "Gold",
+    card: "Card",
+    amount: "amount",
+    arena: "Arena",
+    date: "Date",
+    card_type: "Type",
+    trophies: "trophies",
+    profile: "profile",
+
+    // The cards
+    cards: "Cards",
+    need: "Need",
+    remaining: "Remaining",
+    requests: "Requests",
+    weeks: "Weeks",
+    days: "Days",
+    days_in_order: "Days in order",
+    done_on: "Done on",
+    select_card: "Select {}",
+    select_listed: "Select all listed cards",
+    new_card: "New card",
+    layout: "Layout",
+    layouts: ["Table", "Collection"],
+    level_of: "Level {}",
+    progress: "{} of {} cards",
+    maxed: "Max",
+    upgrade_progress: "Progress",
+    ready_to_upgrade: "Ready to upgrade",
+    upgrade: "Upgrade",
+    upgrade_card: "Upgrade {} to level {}",
+    actions: "Actions",
+    confirm_delete: "Delete {}? It can be restored from the trash. ",
+    confirm_delete_many: "Delete {} cards? They can be restored from the trash. ",
+    trash: "Trash ({})",
+    trash_empty: "The trash is empty",
+    deleted_on: "Deleted on",
+    restore: "Restore",
+    total_needed: "Total needed: ",
+    changed_meanwhile: "Changed meanwhile",
+    level_cap_until: " (King level cap until {})",
+    level_cap: " (King level cap)",
+    planned_order: "Planned order",
+    rarities: ["Common", "Rare", "Epic", "Legendary"],
+    card_types: ["Troop", "Spell", "Building"],
+
+    // The profile
+    selected_arena: "Selected arena: ",
+    arenas: [
+        "Training Camp",
+        "Goblin Stadium",
+        "Bone Pit",
+        "Barbarian Bowl",
+        "P.E.K.K.A's Playhouse",
+        "Spell Valley",
+        "Builder's Workshop",
+        "Royal Arena",
+        "Frozen Peak",
+        "Jungle Arena",
+        "Hog Mountain",
+        "Electro Valley",
+        "Spooky Town",
+        "Rascal's Hideout",
+        "Serenity Peak",
+        "Miner's Mine",
+        "Executioner's Kitchen",
+        "Royal Crypt",
+        "Silent Sanctuary",
+        "Dragon Spa",
+        "Legendary Arena",
+    ],
+    or_trophies: " or trophies: ",
+    sizes: "Request size: {} common, {} rare, {} epic - Donation size: {} common, {} rare (up to {} per day)",
+    other_sources: "Other sources of cards: ",
+    chests: "Chests",
+    shop: "Shop offers",
+    pass_royale: "Pass Royale",
+    challenges: "Challenges",
+    planned_arenas: "Planned arenas: ",
+    arena_on: "{} on {} ",
+    king_level: "King level: ",
+    experience: " XP: ",
+    experience_placeholder: "experience",
+    donation_xp: " XP from donations per week: ",
+    donation_xp_placeholder: "donation XP",
+    next_king_level: "Next King level: {} (cards up to level {}) in {} days on {}",
+    no_king_level_up: "No King level-up planned",
+
+    // Listing, selecting and entering cards
+    search: "search",
+    all_rarities: "All rarities",
+    all_types: "All types",
+    hide_maxed: "Hide maxed cards",
+    deck_only: "Only the deck",
+    selected: "{} selected: ",
+    set_level: "Set level",
+    add_to_deck: "Add to deck",
+    remove_from_deck: "Remove from deck",
+    clear_selection: "Clear selection",
+    add_many: "Add many cards",
+    add_many_help: "One card per line: name, level, amount (optionally followed by rarity and type, which are known for the cards of the game), ",
+    add_many_example: "e.g. \"Knight, 12, 340\" or \"Golem, 9, 20, Epic, Troop\". ",
+    add_many_updates: "Existing cards with the same name get updated.",
+    add_all: "Add all",
+    tab_conflicts: "{} card(s) were changed in another tab at the same time, the latest changes were kept: {}",
+
+    // Requests and transactions
+    today: "Today",
+    requests_made: "Requests made today: {} of {}",
+    epic_done: " (epic done this week)",
+    nothing_to_request: "Nothing left to request today",
+    request_card: "Request {} ({}x)",
+    cards_remaining: " - {}; {} cards remaining",
+    upgrade_on: ", upgrade on {}",
+    requested: "Requested",
+    reason_weekly_epic: "This week's epic request is still open",
+    reason_next_in_order: "Next in the upgrade order",
+    reason_after_upgrade: "{} needs no more requests after today",
+    log_cards: "Log cards: ",
+    donated_instead: "Donated (instead of received from a request)",
+    log: "Log",
+    transaction_stats: "{}: {} requests filled ({}), {} cards donated; ",
+    fill_rate: "{} fill rate",
+    no_fill_rate: "no fill rate yet",
+
+    // Statistics and charts
+    statistics: "Statistics",
+    cards_with_average: "{} cards with an average level of {}",
+    needed_for_max: "Needed for maxing out: {} cards and {} gold ({} complete)",
+    trends_later: "Trends show up after the first week",
+    week_of: "Week of",
+    average_level: "Average level",
+    complete: "Complete",
+    gold_to_max: "Gold to max",
+    timeline: "Timeline",
+    timeline_description: "The day each card gets upgraded",
+    long_gaps: "Highlighted: {} days or more without upgrades",
+    gold_over_time: "Gold needed over time",
+    gold_over_time_description: "The gold needed for all upgrades until each day",
+    chart_days: "{} day(s)",
+    chart_gold: "{} gold",
+    chart_today: "today",
+    chart_in_days: "in {} days",
+
+    // Scenarios and exports
+    scenarios: "What-if scenarios",
+    scenario_name: "scenario name",
+    request_epics: "Request epics",
+    compare: "Compare",
+    current_plan: "Current plan",
+    days_delta: " ({} days)",
+    upgrade_order_label: "Upgrade order",
+    upgrade_orders: ["Closest to the next level first", "Cheapest first", "As listed"],
+    export_calendar: "Export the upgrades to a calendar (.ics)",
+    cannot_export: "Cannot export the calendar: {}",
+    calendar_description: "Cards needed: {} ({} remaining)\nGold needed: {}\nDone in order: {}",
+
+    // Syncing
+    sync_title: "Sync with a server",
+    sync_help: "Keeps the cards of several devices in sync using a self-hosted ",
+    sync_help_profiles: ". Devices using the same profile share their cards.",
+    server: "Server URL",
+    sync: "Sync",
+    syncing: "Syncing...",
+    synced: "Synced",
+    cannot_sync: "Cannot sync: {}",
+
+    // Errors
+    error_invalid_level: "Level {} is invalid for {} cards",
+    error_date_overflow: "The predicted date is out of range",
+    error_unknown_card: "No card with the UUID {}",
+    error_not_enough_cards: "Cannot take away {} cards from the {} available",
+    error_level_capped: "Level {} needs a higher King level than {}",
+    error_invalid_line: "Line {}: {}",
+    error_migration_failed: "Cannot migrate the data: {}",
+    error_event_log_conflict: "Conflicting event: {}",
+    error_invalid_profile: "Invalid profile name \"{}\" (use up to 64 letters, digits, - and _)",
+    line_value_count: "expected 3 to 5 values, found {}",
+    line_empty_name: "the name is empty",
+    line_level_not_a_number: "the level \"{}\" is not a number",
+    line_amount_not_a_number: "the amount \"{}\" is not a number",
+    line_unknown_rarity: "unknown rarity \"{}\"",
+    line_unknown_type: "unknown type \"{}\"",
+};
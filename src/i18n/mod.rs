@@ -0,0 +1,440 @@
+/*!
+Translations of the user interface and locale-aware formatting of numbers and dates
+
+Each locale has a message catalog (see `Messages`).
+Messages with values contain `{}` placeholders, which get replaced using `fill`.
+*/
+
+use crate::logic::{
+    error::{Error, LineProblem},
+    recommendation::Reason,
+    scenario::UpgradeOrder,
+    settings::{Layout, Theme},
+    types::{Arena, CardType, Rarity},
+};
+use chrono::NaiveDate;
+use std::fmt::Display;
+use strum::IntoEnumIterator;
+
+pub use crate::logic::settings::Locale;
+
+mod de;
+mod en;
+
+impl Locale {
+    /// Returns the message catalog of the locale
+    pub fn messages(self) -> &'static Messages {
+        match self {
+            Locale::English => &en::MESSAGES,
+            Locale::German => &de::MESSAGES,
+        }
+    }
+
+    /// Returns the name of the language (in the language itself)
+    pub fn name(self) -> &'static str {
+        match self {
+            Locale::English => "English",
+            Locale::German => "Deutsch",
+        }
+    }
+
+    /// Picks the locale of a language tag (like `de-AT`), defaulting to English
+    pub fn from_language_tag(tag: &str) -> Self {
+        match tag.split('-').next().map(str::to_lowercase).as_deref() {
+            Some("de") => Locale::German,
+            _ => Locale::English,
+        }
+    }
+
+    /// Returns the separators of thousands and decimals
+    fn separators(self) -> (char, char) {
+        match self {
+            Locale::English => (',', '.'),
+            Locale::German => ('.', ','),
+        }
+    }
+
+    /// Formats a whole number, grouping its thousands (like `12,345`)
+    pub fn format_integer(self, number: usize) -> String {
+        let (thousands, _) = self.separators();
+        let digits = number.to_string();
+
+        let mut grouped = String::new();
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                grouped.push(thousands);
+            }
+            grouped.push(digit);
+        }
+
+        grouped
+    }
+
+    /// Formats a number with up to `decimals` decimal places, dropping trailing zeros
+    pub fn format_decimal(self, number: f64, decimals: usize) -> String {
+        if !number.is_finite() {
+            return "∞".to_owned();
+        }
+
+        let (_, decimal) = self.separators();
+        let rounded = format!("{:.*}", decimals, number.abs());
+        let (whole, fraction) = rounded.split_once('.').unwrap_or((&rounded, ""));
+        let fraction = fraction.trim_end_matches('0');
+
+        // Unwraps safely because the rounded number only has digits before the point
+        let mut formatted = self.format_integer(whole.parse().unwrap());
+        if !fraction.is_empty() {
+            formatted.push(decimal);
+            formatted.push_str(fraction);
+        }
+
+        if number < 0. && formatted.chars().any(|c| c.is_ascii_digit() && c != '0') {
+            formatted.insert(0, '-');
+        }
+
+        formatted
+    }
+
+    /// Formats a difference, always showing its sign (like `+1.5`)
+    pub fn format_signed(self, number: f64, decimals: usize) -> String {
+        let formatted = self.format_decimal(number, decimals);
+
+        if formatted.starts_with('-') {
+            formatted
+        } else {
+            format!("+{}", formatted)
+        }
+    }
+
+    /// Formats a ratio (like `0.125`) as a percentage
+    pub fn format_percent(self, ratio: f64, decimals: usize) -> String {
+        let number = self.format_decimal(ratio * 100., decimals);
+
+        match self {
+            Locale::English => format!("{}%", number),
+            Locale::German => format!("{} %", number),
+        }
+    }
+
+    /// Formats an amount of gold, abbreviating thousands and millions (like `12.5K`)
+    pub fn format_gold(self, gold: usize) -> String {
+        let (thousands, millions) = match self {
+            Locale::English => ("K", "M"),
+            Locale::German => (" Tsd.", " Mio."),
+        };
+
+        if gold < 1000 {
+            gold.to_string()
+        } else if gold < 1_000_000 {
+            format!("{}{}", self.format_decimal(gold as f64 / 1e3, 1), thousands)
+        } else {
+            format!("{}{}", self.format_decimal(gold as f64 / 1e6, 2), millions)
+        }
+    }
+
+    /// Formats a date (like `Mar 5, 2021` or `05.03.2021`)
+    pub fn format_date(self, date: NaiveDate) -> String {
+        match self {
+            Locale::English => date.format("%b %-d, %Y").to_string(),
+            Locale::German => date.format("%d.%m.%Y").to_string(),
+        }
+    }
+}
+
+/// Replaces the `{}` placeholders of a message with the given values (in their order)
+pub fn fill(message: &str, values: &[&dyn Display]) -> String {
+    let mut parts = message.split("{}");
+    let mut filled = parts.next().unwrap_or_default().to_owned();
+
+    for (i, part) in parts.enumerate() {
+        if let Some(value) = values.get(i) {
+            filled.push_str(&value.to_string());
+        }
+        filled.push_str(part);
+    }
+
+    filled
+}
+
+/// The texts of the user interface in one language
+pub struct Messages {
+    // The app
+    pub about: &'static str,
+    pub version: &'static str,
+    pub edit_hint: &'static str,
+    pub source_code: &'static str,
+    pub source_code_link: &'static str,
+    pub trademark: &'static str,
+    pub license: &'static str,
+    pub language: &'static str,
+    pub theme: &'static str,
+    pub themes: [&'static str; 2],
+    pub update_available: &'static str,
+    pub reload: &'static str,
+    pub later: &'static str,
+
+    // Common words
+    pub not_available: &'static str,
+    pub add: &'static str,
+    pub remove: &'static str,
+    pub save: &'static str,
+    pub overwrite: &'static str,
+    pub cancel: &'static str,
+    pub delete: &'static str,
+    pub error: &'static str,
+    pub name: &'static str,
+    pub level: &'static str,
+    pub have: &'static str,
+    pub rarity: &'static str,
+    pub gold: &'static str,
+    pub card: &'static str,
+    pub amount: &'static str,
+    pub arena: &'static str,
+    pub date: &'static str,
+    pub card_type: &'static str,
+    pub trophies: &'static str,
+    pub profile: &'static str,
+
+    // The cards
+    pub cards: &'static str,
+    pub need: &'static str,
+    pub remaining: &'static str,
+    pub requests: &'static str,
+    pub weeks: &'static str,
+    pub days: &'static str,
+    pub days_in_order: &'static str,
+    pub done_on: &'static str,
+    pub select_card: &'static str,
+    pub select_listed: &'static str,
+    pub new_card: &'static str,
+    pub layout: &'static str,
+    pub layouts: [&'static str; 2],
+    pub level_of: &'static str,
+    pub progress: &'static str,
+    pub maxed: &'static str,
+    pub upgrade_progress: &'static str,
+    pub ready_to_upgrade: &'static str,
+    pub upgrade: &'static str,
+    pub upgrade_card: &'static str,
+    pub actions: &'static str,
+    pub confirm_delete: &'static str,
+    pub confirm_delete_many: &'static str,
+    pub trash: &'static str,
+    pub trash_empty: &'static str,
+    pub deleted_on: &'static str,
+    pub restore: &'static str,
+    pub total_needed: &'static str,
+    pub changed_meanwhile: &'static str,
+    pub level_cap_until: &'static str,
+    pub level_cap: &'static str,
+    pub planned_order: &'static str,
+    pub rarities: [&'static str; 4],
+    pub card_types: [&'static str; 3],
+
+    // The profile
+    pub selected_arena: &'static str,
+    pub arenas: [&'static str; 21],
+    pub or_trophies: &'static str,
+    pub sizes: &'static str,
+    pub other_sources: &'static str,
+    pub chests: &'static str,
+    pub shop: &'static str,
+    pub pass_royale: &'static str,
+    pub challenges: &'static str,
+    pub planned_arenas: &'static str,
+    pub arena_on: &'static str,
+    pub king_level: &'static str,
+    pub experience: &'static str,
+    pub experience_placeholder: &'static str,
+    pub donation_xp: &'static str,
+    pub donation_xp_placeholder: &'static str,
+    pub next_king_level: &'static str,
+    pub no_king_level_up: &'static str,
+
+    // Listing, selecting and entering cards
+    pub search: &'static str,
+    pub all_rarities: &'static str,
+    pub all_types: &'static str,
+    pub hide_maxed: &'static str,
+    pub deck_only: &'static str,
+    pub selected: &'static str,
+    pub set_level: &'static str,
+    pub add_to_deck: &'static str,
+    pub remove_from_deck: &'static str,
+    pub clear_selection: &'static str,
+    pub add_many: &'static str,
+    pub add_many_help: &'static str,
+    pub add_many_example: &'static str,
+    pub add_many_updates: &'static str,
+    pub add_all: &'static str,
+    pub tab_conflicts: &'static str,
+
+    // Requests and transactions
+    pub today: &'static str,
+    pub requests_made: &'static str,
+    pub epic_done: &'static str,
+    pub nothing_to_request: &'static str,
+    pub request_card: &'static str,
+    pub cards_remaining: &'static str,
+    pub upgrade_on: &'static str,
+    pub requested: &'static str,
+    pub reason_weekly_epic: &'static str,
+    pub reason_next_in_order: &'static str,
+    pub reason_after_upgrade: &'static str,
+    pub log_cards: &'static str,
+    pub donated_instead: &'static str,
+    pub log: &'static str,
+    pub transaction_stats: &'static str,
+    pub fill_rate: &'static str,
+    pub no_fill_rate: &'static str,
+
+    // Statistics and charts
+    pub statistics: &'static str,
+    pub cards_with_average: &'static str,
+    pub needed_for_max: &'static str,
+    pub trends_later: &'static str,
+    pub week_of: &'static str,
+    pub average_level: &'static str,
+    pub complete: &'static str,
+    pub gold_to_max: &'static str,
+    pub timeline: &'static str,
+    pub timeline_description: &'static str,
+    pub long_gaps: &'static str,
+    pub gold_over_time: &'static str,
+    pub gold_over_time_description: &'static str,
+    pub chart_days: &'static str,
+    pub chart_gold: &'static str,
+    pub chart_today: &'static str,
+    pub chart_in_days: &'static str,
+
+    // Scenarios and exports
+    pub scenarios: &'static str,
+    pub scenario_name: &'static str,
+    pub request_epics: &'static str,
+    pub compare: &'static str,
+    pub current_plan: &'static str,
+    pub days_delta: &'static str,
+    pub upgrade_order_label: &'static str,
+    pub upgrade_orders: [&'static str; 3],
+    pub export_calendar: &'static str,
+    pub cannot_export: &'static str,
+    pub calendar_description: &'static str,
+
+    // Syncing
+    pub sync_title: &'static str,
+    pub sync_help: &'static str,
+    pub sync_help_profiles: &'static str,
+    pub server: &'static str,
+    pub sync: &'static str,
+    pub syncing: &'static str,
+    pub synced: &'static str,
+    pub cannot_sync: &'static str,
+
+    // Errors
+    pub error_invalid_level: &'static str,
+    pub error_date_overflow: &'static str,
+    pub error_unknown_card: &'static str,
+    pub error_not_enough_cards: &'static str,
+    pub error_level_capped: &'static str,
+    pub error_invalid_line: &'static str,
+    pub error_migration_failed: &'static str,
+    pub error_event_log_conflict: &'static str,
+    pub error_invalid_profile: &'static str,
+    pub line_value_count: &'static str,
+    pub line_empty_name: &'static str,
+    pub line_level_not_a_number: &'static str,
+    pub line_amount_not_a_number: &'static str,
+    pub line_unknown_rarity: &'static str,
+    pub line_unknown_type: &'static str,
+}
+
+impl Messages {
+    pub fn rarity(&self, rarity: &Rarity) -> &'static str {
+        match rarity {
+            Rarity::Common => self.rarities[0],
+            Rarity::Rare => self.rarities[1],
+            Rarity::Epic => self.rarities[2],
+            Rarity::Legendary => self.rarities[3],
+        }
+    }
+
+    pub fn arena(&self, arena: &Arena) -> &'static str {
+        // The names are listed in the order of the arenas
+        Arena::iter()
+            .position(|other| other == *arena)
+            .map_or("", |index| self.arenas[index])
+    }
+
+    pub fn card_type(&self, card_type: &CardType) -> &'static str {
+        match card_type {
+            CardType::Troop => self.card_types[0],
+            CardType::Spell => self.card_types[1],
+            CardType::Building => self.card_types[2],
+        }
+    }
+
+    pub fn theme(&self, theme: &Theme) -> &'static str {
+        match theme {
+            Theme::Dark => self.themes[0],
+            Theme::Light => self.themes[1],
+        }
+    }
+
+    pub fn layout(&self, layout: &Layout) -> &'static str {
+        match layout {
+            Layout::Table => self.layouts[0],
+            Layout::Collection => self.layouts[1],
+        }
+    }
+
+    pub fn reason(&self, reason: &Reason) -> String {
+        match reason {
+            Reason::WeeklyEpic => self.reason_weekly_epic.to_owned(),
+            Reason::NextInOrder => self.reason_next_in_order.to_owned(),
+            Reason::AfterUpgrade { previous } => fill(self.reason_after_upgrade, &[previous]),
+        }
+    }
+
+    pub fn error(&self, error: &Error) -> String {
+        match error {
+            Error::InvalidLevel { level, rarity } => {
+                fill(self.error_invalid_level, &[level, &self.rarity(rarity)])
+            }
+            Error::DateOverflow => self.error_date_overflow.to_owned(),
+            Error::UnknownCard(uuid) => fill(self.error_unknown_card, &[uuid]),
+            Error::NotEnoughCards { have, amount } => {
+                fill(self.error_not_enough_cards, &[amount, have])
+            }
+            Error::LevelCapped { level, king_level } => {
+                fill(self.error_level_capped, &[level, king_level])
+            }
+            Error::InvalidLine { line, problem } => fill(
+                self.error_invalid_line,
+                &[line, &self.line_problem(problem)],
+            ),
+            Error::MigrationFailed(cause) => fill(self.error_migration_failed, &[cause]),
+            Error::EventLogConflict(cause) => fill(self.error_event_log_conflict, &[cause]),
+            Error::InvalidProfile(profile) => fill(self.error_invalid_profile, &[profile]),
+        }
+    }
+
+    fn line_problem(&self, problem: &LineProblem) -> String {
+        match problem {
+            LineProblem::ValueCount(count) => fill(self.line_value_count, &[count]),
+            LineProblem::EmptyName => self.line_empty_name.to_owned(),
+            LineProblem::LevelNotANumber(field) => fill(self.line_level_not_a_number, &[field]),
+            LineProblem::AmountNotANumber(field) => fill(self.line_amount_not_a_number, &[field]),
+            LineProblem::UnknownRarity(field) => fill(self.line_unknown_rarity, &[field]),
+            LineProblem::UnknownType(field) => fill(self.line_unknown_type, &[field]),
+            LineProblem::InvalidCard(error) => self.error(error),
+        }
+    }
+
+    pub fn upgrade_order(&self, order: &UpgradeOrder) -> &'static str {
+        match order {
+            UpgradeOrder::ByRemaining => self.upgrade_orders[0],
+            UpgradeOrder::ByGold => self.upgrade_orders[1],
+            UpgradeOrder::AsListed => self.upgrade_orders[2],
+        }
+    }
+}
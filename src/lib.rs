@@ -1,8 +1,13 @@
-#![recursion_limit = "512"]
+#![recursion_limit = "1024"]
 
 // Export as library
+pub mod i18n;
 pub mod logic;
 
+// The self-hosted sync server
+#[cfg(feature = "server")]
+pub mod server;
+
 // For use in the web app only
 pub(crate) mod components;
 pub(crate) mod constants;
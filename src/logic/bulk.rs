@@ -0,0 +1,157 @@
+use super::{
+    catalog::find,
+    error::{Error, LineProblem, Result},
+    types::{CardEntry, CardType, Rarity},
+};
+use std::str::FromStr;
+
+/**
+A card entered as a line of text, like `Knight, 12, 340`
+
+The line contains the name, level and amount of cards,
+optionally followed by the rarity and the type of the card.
+*/
+#[derive(Debug, PartialEq, Clone)]
+pub struct BulkCard {
+    pub name: String,
+    pub level: usize,
+    pub have: usize,
+    pub rarity: Option<Rarity>,
+    pub card_type: Option<CardType>,
+}
+
+impl BulkCard {
+    /// Returns a copy of an existing card with the entered values
+    pub fn apply_to(&self, card: &CardEntry) -> CardEntry {
+        let mut card = card.clone();
+
+        card.name = self.name.clone();
+        card.level = self.level;
+        card.have = self.have;
+
+        if let Some(rarity) = &self.rarity {
+            card.rarity = rarity.clone();
+        }
+
+        if let Some(card_type) = &self.card_type {
+            card.card_type = card_type.clone();
+        }
+
+        card
+    }
+
+    /// Creates a new card with the entered values, looking up the missing ones in the catalog
+    pub fn to_card(&self) -> CardEntry {
+        let mut card = CardEntry::new();
+
+        if let Some(catalog_card) = find(&self.name) {
+            card.rarity = catalog_card.rarity.clone();
+            card.card_type = catalog_card.card_type.clone();
+        }
+
+        self.apply_to(&card)
+    }
+}
+
+/// Parses a single line (numbered from 1)
+fn parse_line(number: usize, line: &str) -> Result<BulkCard> {
+    let invalid = |problem: LineProblem| Error::InvalidLine {
+        line: number,
+        problem,
+    };
+
+    let fields = line.split(',').map(str::trim).collect::<Vec<_>>();
+
+    if fields.len() < 3 || fields.len() > 5 {
+        return Err(invalid(LineProblem::ValueCount(fields.len())));
+    }
+
+    if fields[0].is_empty() {
+        return Err(invalid(LineProblem::EmptyName));
+    }
+
+    let parse_number = |field: &str, problem: fn(String) -> LineProblem| {
+        field
+            .parse::<usize>()
+            .map_err(|_| invalid(problem(field.to_owned())))
+    };
+
+    let card = BulkCard {
+        name: fields[0].to_owned(),
+        level: parse_number(fields[1], LineProblem::LevelNotANumber)?,
+        have: parse_number(fields[2], LineProblem::AmountNotANumber)?,
+        rarity: match fields.get(3) {
+            Some(field) => Some(
+                Rarity::from_str(field)
+                    .map_err(|_| invalid(LineProblem::UnknownRarity(field.to_string())))?,
+            ),
+            None => None,
+        },
+        card_type: match fields.get(4) {
+            Some(field) => Some(
+                CardType::from_str(field)
+                    .map_err(|_| invalid(LineProblem::UnknownType(field.to_string())))?,
+            ),
+            None => None,
+        },
+    };
+
+    // Check the level for the entered rarity (the others are checked against the changed card)
+    if card.rarity.is_some() {
+        card.to_card()
+            .check_level()
+            .map_err(|e| invalid(LineProblem::InvalidCard(Box::new(e))))?;
+    }
+
+    Ok(card)
+}
+
+/**
+Parses cards entered as lines of text (see `BulkCard`)
+
+Returns the number (counted from 1) and result of each non-empty line,
+so the valid lines can be used while reporting the others.
+*/
+pub fn parse_bulk(text: &str) -> Vec<(usize, Result<BulkCard>)> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| (i + 1, parse_line(i + 1, line)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_the_lines() {
+        let results = parse_bulk("Knight, 12, 340\n\nGolem, 9\nArchers, 11, 20");
+
+        assert_eq!(
+            results
+                .iter()
+                .map(|(number, _)| *number)
+                .collect::<Vec<_>>(),
+            vec![1, 3, 4]
+        );
+        assert!(results[1].1.is_err());
+    }
+
+    #[test]
+    fn looks_up_missing_values_in_the_catalog() {
+        let (_, result) = parse_bulk("Golem, 9, 20").remove(0);
+        let card = result.unwrap().to_card();
+
+        assert_eq!(card.rarity, Rarity::Epic);
+        assert_eq!(card.card_type, CardType::Troop);
+    }
+
+    #[test]
+    fn checks_the_level_of_entered_rarities() {
+        assert!(parse_bulk("Golem, 3, 20, Epic").remove(0).1.is_err());
+
+        // Without a rarity, the level is checked against the changed card
+        assert!(parse_bulk("Golem, 3, 20").remove(0).1.is_ok());
+    }
+}
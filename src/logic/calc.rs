@@ -1,14 +1,11 @@
-use super::types::{get_request_size, Arena, CardEntry, Rarity, REQUEST_FREQUENCY};
-use anyhow::{bail, Result};
+use super::{
+    error::{Error, Result},
+    experience::get_card_level_cap,
+    profile::Profile,
+    types::{get_request_size, Arena, CardEntry, Rarity, MAX_LEVEL},
+};
 use chrono::{DateTime, Duration, Local};
 use std::cmp;
-use thiserror::Error;
-
-#[derive(Error, Debug)]
-enum MyError {
-    #[error("One or more cards have missing values")]
-    MissingCalculatedValues,
-}
 
 #[derive(PartialEq, Clone)]
 pub struct CardData {
@@ -19,46 +16,103 @@ pub struct CardData {
     pub done_on: DateTime<Local>,
     pub days_in_order: Option<f64>,
     pub done_in_order_on: Option<DateTime<Local>>,
+    /// Whether the current King level allows the upgrade (see `Planner::level_cap` for when it will)
+    pub level_cap: LevelCap,
+
+    /// The share of the cards needed for the next level which are available
+    pub progress: f64,
+
+    /// Whether the card can be upgraded right now (enough cards and no King level cap)
+    pub ready_to_upgrade: bool,
+}
+
+/// Whether the King level allows upgrading a card to the next level
+#[derive(PartialEq, Clone, Debug)]
+pub enum LevelCap {
+    /// The upgrade is possible at the current King level
+    Allowed,
+
+    /// The upgrade needs a higher King level, which is predicted to be reached on the given day
+    LiftedOn(DateTime<Local>),
+
+    /// The upgrade needs a higher King level, which is not predicted to be reached
+    Blocked,
 }
 
 impl CardEntry {
-    pub fn calc_remaining(&self, arena: Option<&Arena>) -> Option<CardData> {
-        // Cannot request legendary cards
-        if self.rarity == Rarity::Legendary {
-            return None;
-        }
+    /// Predicts when the card can be upgraded (`None` for cards which never receive any cards)
+    pub fn calc_remaining(&self, profile: &Profile) -> Result<Option<CardData>> {
+        let cards_remaining = self.get_needed_cards()?.saturating_sub(self.have);
 
-        // The arena the user is in (default to the LegendaryArena)
-        let request_size = get_request_size(&arena.unwrap_or(&Arena::LegendaryArena));
+        let request_frequency = profile.schedule.get(&self.rarity);
 
-        let cards_remaining = if self.get_needed_cards() < self.have {
-            0
-        } else {
-            self.get_needed_cards() - self.have
-        };
+        // The arenas the user is going to be in
+        let schedule = profile.arena_schedule(Local::now().date_naive());
+
+        let mut cards_left = cards_remaining as f64;
+        let mut weeks_remaining = 0.;
+        let mut requests_made = 0.;
+
+        for (i, (start, arena)) in schedule.iter().enumerate() {
+            // Nothing left to collect (like on the highest level)
+            if cards_left <= 0. {
+                break;
+            }
+
+            let (cards_per_request, income) = self.rates_in(arena, profile);
+
+            // Check if the card gets done before the next arena is reached
+            if let Some((end, _)) = schedule.get(i + 1) {
+                let weeks = end - start;
+                let cards_gained = (cards_per_request * request_frequency + income) * weeks;
+
+                if cards_gained < cards_left {
+                    cards_left -= cards_gained;
+
+                    // Cards which can't be requested (like legendary ones) only use other sources
+                    if cards_per_request > 0. {
+                        requests_made += request_frequency * weeks;
+                    }
+                    continue;
+                }
+            }
+
+            // The card never gets done if it doesn't receive any cards
+            if cards_per_request * request_frequency + income <= 0. {
+                return Ok(None);
+            }
+
+            let requests_needed = (cards_left / cards_per_request).ceil();
 
-        let requests_remaining = (cards_remaining as f64
-            / if self.rarity == Rarity::Common {
-                request_size.common as f64
+            let (weeks, requests) = if income > 0. {
+                // Other sources fill up the remaining cards alongside requests
+                let weeks = cards_left / (cards_per_request * request_frequency + income);
+
+                let requests = if cards_per_request > 0. {
+                    (weeks * request_frequency).ceil().min(requests_needed)
+                } else {
+                    0.
+                };
+
+                (weeks, requests)
             } else {
-                request_size.rare as f64
-            })
-        .ceil() as usize;
+                (requests_needed / request_frequency, requests_needed)
+            };
+
+            weeks_remaining = start + weeks;
+            requests_made += requests;
+            break;
+        }
+
+        let requests_remaining = requests_made.ceil() as usize;
 
-        let weeks_remaining = requests_remaining as f64
-            / match self.rarity {
-                Rarity::Common => REQUEST_FREQUENCY.common,
-                Rarity::Rare => REQUEST_FREQUENCY.rare,
-                Rarity::Epic => REQUEST_FREQUENCY.epic,
-                Rarity::Legendary => REQUEST_FREQUENCY.legendary, // Unreachable
-            } as f64;
+        let days_remaining = weeks_remaining * 7.;
 
-        let days_remaining = weeks_remaining * 7 as f64;
+        let done_on = days_from_now(days_remaining)?;
 
-        let done_on =
-            Local::now().checked_add_signed(Duration::days(days_remaining.ceil() as i64))?;
+        let level_cap = self.level_cap_at(profile.king.level);
 
-        Some(CardData {
+        Ok(Some(CardData {
             cards_remaining,
             requests_remaining,
             weeks_remaining,
@@ -66,76 +120,256 @@ impl CardEntry {
             done_on,
             days_in_order: None,
             done_in_order_on: None,
-        })
+            progress: self.get_upgrade_progress()?,
+            ready_to_upgrade: self.has_enough_cards()? && level_cap == LevelCap::Allowed,
+            level_cap,
+        }))
+    }
+
+    /// Checks if the card can be upgraded right now (see `CardData::ready_to_upgrade`)
+    pub fn is_ready_to_upgrade(&self, king_level: usize) -> bool {
+        match &self.computed {
+            Some(data) => data.ready_to_upgrade,
+            // Cards which never receive any have no computed values
+            None => {
+                self.has_enough_cards().unwrap_or_default()
+                    && self.level_cap_at(king_level) == LevelCap::Allowed
+            }
+        }
     }
 
-    pub fn compute_all(list: &mut Vec<Self>, arena: Option<&Arena>) {
+    /// Checks if a King level allows upgrading the card to the next level
+    pub fn level_cap_at(&self, king_level: usize) -> LevelCap {
+        if self.level < MAX_LEVEL && self.level + 1 > get_card_level_cap(king_level) {
+            LevelCap::Blocked
+        } else {
+            LevelCap::Allowed
+        }
+    }
+
+    /// Returns the cards per request and the cards per week from other sources in an arena
+    fn rates_in(&self, arena: &Arena, profile: &Profile) -> (f64, f64) {
+        // Requests often don't get filled completely
+        let cards_per_request =
+            get_request_size(arena).get(&self.rarity) as f64 * profile.fill_rate.get(&self.rarity);
+
+        // The cards per week from sources other than requests
+        let income = profile.income.cards_per_week(arena).get(&self.rarity);
+
+        (cards_per_request, income)
+    }
+
+    pub fn compute_all(list: &mut [Self], profile: &Profile) -> Result<()> {
         for card in list {
-            card.computed = card.calc_remaining(arena);
+            card.computed = card.calc_remaining(profile)?;
         }
+
+        Ok(())
     }
 
     /// Custom order algorithm for sorting CardEntries by days
     //  FnMut(&Self, &Self) -> cmp::Ordering
-    pub fn sort_by_remaining(
-        arena: Option<&Arena>,
-    ) -> impl FnMut(&Self, &Self) -> cmp::Ordering + '_ {
+    pub fn sort_by_remaining(profile: &Profile) -> impl FnMut(&Self, &Self) -> cmp::Ordering + '_ {
         move |a: &Self, b: &Self| {
-            // Handle legendary cards
-            if a.rarity == Rarity::Legendary {
-                if b.rarity == Rarity::Legendary {
-                    return cmp::Ordering::Equal;
-                } else {
-                    return cmp::Ordering::Greater;
-                }
-            }
-            if b.rarity == Rarity::Legendary {
-                return cmp::Ordering::Less;
-            }
-
-            let get_remaining = |card: &CardEntry| {
-                card.computed
-                    .clone()
-                    .unwrap_or_else(|| card.calc_remaining(arena).unwrap())
-                    .days_remaining
+            // Cards which cannot be computed are sorted last
+            let get_remaining = |card: &CardEntry| match &card.computed {
+                Some(data) => data.days_remaining,
+                None => match card.calc_remaining(profile) {
+                    Ok(Some(data)) => data.days_remaining,
+                    _ => f64::INFINITY,
+                },
             };
 
             // Compare the cards
-            get_remaining(a).partial_cmp(&get_remaining(b)).unwrap()
+            get_remaining(a).total_cmp(&get_remaining(b))
         }
     }
 
-    pub fn sum_all(list: &mut Vec<Self>) -> Result<()> {
-        let mut prev_time_regular = 0.;
-        let mut prev_time_epic = 0.;
+    pub fn sum_all(list: &mut [Self]) -> Result<()> {
+        Self::sum_from(list, 0)
+    }
 
-        for card in list {
+    /// Computes the in_order values of all cards starting at a given index,
+    /// continuing the sums of the (already summed) cards before it
+    pub fn sum_from(list: &mut [Self], start: usize) -> Result<()> {
+        // Continue from the latest sums of each group before the start index
+        let prev_sum = |group: fn(&Rarity) -> bool| {
+            list[..start]
+                .iter()
+                .rev()
+                .filter(|card| group(&card.rarity))
+                .find_map(|card| card.computed.as_ref().and_then(|data| data.days_in_order))
+                .unwrap_or(0.)
+        };
+
+        let mut prev_time_regular = prev_sum(|r| matches!(r, Rarity::Common | Rarity::Rare));
+        let mut prev_time_epic = prev_sum(|r| *r == Rarity::Epic);
+
+        for card in &mut list[start..] {
             // Handle cards according to their respective rarities
             let prev_time = match card.rarity {
                 Rarity::Common | Rarity::Rare => &mut prev_time_regular,
                 Rarity::Epic => &mut prev_time_epic,
                 Rarity::Legendary => {
-                    // Skip legendary cards
+                    // Skip legendary cards, which don't take up any requests
                     continue;
                 }
             };
 
+            // Skip cards which never get done
             if let Some(data) = &mut card.computed {
                 let current_time = data.days_remaining + *prev_time;
 
-                data.done_in_order_on = Some(
-                    Local::now()
-                        .checked_add_signed(Duration::days(current_time.ceil() as i64))
-                        .ok_or(MyError::MissingCalculatedValues)?,
-                );
+                data.done_in_order_on = Some(days_from_now(current_time)?);
 
                 data.days_in_order = Some(current_time);
                 *prev_time = current_time;
-            } else {
-                bail!(MyError::MissingCalculatedValues);
             }
         }
 
         Ok(())
     }
 }
+
+/// Returns the date a given (fractional) amount of days from now on
+pub fn days_from_now(days: f64) -> Result<DateTime<Local>> {
+    Duration::try_days(days.ceil() as i64)
+        .and_then(|duration| Local::now().checked_add_signed(duration))
+        .ok_or(Error::DateOverflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::{planner::Planner, profile::ArenaMilestone, types::Arena};
+
+    fn card(rarity: Rarity, level: usize, have: usize) -> CardEntry {
+        CardEntry {
+            rarity,
+            level,
+            have,
+            ..CardEntry::new()
+        }
+    }
+
+    /// A profile without any requests or other sources of cards
+    fn profile_without_cards() -> Profile {
+        let mut profile = Profile::new(Arena::TrainingCamp);
+        profile.schedule.common = 0.;
+        profile.schedule.epic = 0.;
+
+        profile
+    }
+
+    #[test]
+    fn maxed_card_without_cards_is_done() {
+        let data = card(Rarity::Epic, MAX_LEVEL, 0)
+            .calc_remaining(&profile_without_cards())
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(data.cards_remaining, 0);
+        assert_eq!(data.requests_remaining, 0);
+        assert_eq!(data.days_remaining, 0.);
+    }
+
+    #[test]
+    fn card_without_cards_is_never_done() {
+        let data = card(Rarity::Common, 9, 0)
+            .calc_remaining(&profile_without_cards())
+            .unwrap();
+
+        assert!(data.is_none());
+    }
+
+    #[test]
+    fn plans_cards_without_cards() {
+        let cards = vec![
+            card(Rarity::Common, 9, 0),
+            card(Rarity::Common, MAX_LEVEL, 0),
+            card(Rarity::Epic, MAX_LEVEL, 0),
+            card(Rarity::Legendary, 9, 0),
+        ];
+
+        let planner = Planner::new(cards, profile_without_cards()).unwrap();
+
+        // The done cards go first, the ones which never get done last
+        assert_eq!(planner.cards()[0].level, MAX_LEVEL);
+        assert_eq!(planner.cards()[1].level, MAX_LEVEL);
+        assert!(planner.cards()[2].computed.is_none());
+        assert!(planner.cards()[3].computed.is_none());
+    }
+
+    #[test]
+    fn king_level_caps_upgrades_without_computed_values() {
+        let legendary = card(Rarity::Legendary, 12, 1000);
+
+        assert!(legendary.computed.is_none());
+        assert!(!legendary.is_ready_to_upgrade(12));
+        assert!(matches!(
+            legendary.upgraded(12),
+            Err(Error::LevelCapped {
+                level: 13,
+                king_level: 12
+            })
+        ));
+
+        assert!(legendary.is_ready_to_upgrade(13));
+        assert_eq!(legendary.upgraded(13).unwrap().level, 13);
+    }
+
+    #[test]
+    fn plans_income_only_cards_without_requests() {
+        let mut profile = profile_without_cards();
+        profile.income.pass_royale = true;
+
+        let legendary = card(Rarity::Legendary, 9, 0);
+        let data = legendary.calc_remaining(&profile).unwrap().unwrap();
+
+        let cards_remaining = legendary.get_needed_cards().unwrap();
+        let income = profile.income.rates.pass_royale.legendary;
+
+        assert_eq!(data.cards_remaining, cards_remaining);
+        assert_eq!(data.requests_remaining, 0);
+        assert!((data.weeks_remaining - cards_remaining as f64 / income).abs() < 1e-9);
+    }
+
+    #[test]
+    fn uses_the_request_sizes_of_later_arenas() {
+        // One epic card per week, then four after two weeks
+        let mut profile = Profile::new(Arena::GoblinStadium);
+        profile.progression.push(ArenaMilestone {
+            arena: Arena::LegendaryArena,
+            date: Local::now().date_naive() + Duration::days(14),
+        });
+
+        // Needs 20 cards
+        let data = card(Rarity::Epic, 9, 0)
+            .calc_remaining(&profile)
+            .unwrap()
+            .unwrap();
+
+        // The remaining 18 cards take five whole requests of four
+        assert_eq!(data.cards_remaining, 20);
+        assert_eq!(data.weeks_remaining, 2. + 5.);
+        assert_eq!(data.requests_remaining, 2 + 5);
+    }
+
+    #[test]
+    fn cards_done_before_a_milestone_ignore_it() {
+        let mut profile = Profile::new(Arena::GoblinStadium);
+        profile.progression.push(ArenaMilestone {
+            arena: Arena::LegendaryArena,
+            date: Local::now().date_naive() + Duration::days(14),
+        });
+
+        // Needs 2 more cards, one per week
+        let data = card(Rarity::Epic, 9, 18)
+            .calc_remaining(&profile)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(data.weeks_remaining, 2.);
+        assert_eq!(data.requests_remaining, 2);
+    }
+}
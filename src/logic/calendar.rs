@@ -0,0 +1,180 @@
+use super::{calc::CardData, error::Result, types::CardEntry};
+use crate::constants::meta;
+use chrono::{Duration, NaiveDate, Utc};
+
+/// The maximum length of a line in an iCalendar file (in bytes, excluding the line break)
+const MAX_LINE_LENGTH: usize = 75;
+
+/**
+Generates an iCalendar (.ics) file of the planned upgrades
+
+Every card with an in-order date (see `CardEntry::sum_all`) gets an all-day event
+on that date. `describe` returns the summary and the description of the event of a card
+on a given date (in the language of the user).
+*/
+pub fn to_ical(
+    cards: &[CardEntry],
+    describe: impl Fn(&CardEntry, &CardData, NaiveDate) -> Result<(String, String)>,
+) -> Result<String> {
+    let timestamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_owned(),
+        "VERSION:2.0".to_owned(),
+        format!(
+            "PRODID:-//{name}//{name} {}//EN",
+            meta::VERSION,
+            name = meta::NAME
+        ),
+        "CALSCALE:GREGORIAN".to_owned(),
+    ];
+
+    for card in cards {
+        let data = match &card.computed {
+            Some(data) => data,
+            None => continue,
+        };
+
+        let date = match data.done_in_order_on {
+            Some(date) => date.date_naive(),
+            None => continue,
+        };
+
+        let (summary, description) = describe(card, data, date)?;
+
+        lines.push("BEGIN:VEVENT".to_owned());
+        lines.push(format!(
+            "UID:{}-{}@{}",
+            card.uuid,
+            card.level + 1,
+            meta::NAME
+        ));
+        lines.push(format!("DTSTAMP:{}", timestamp));
+        lines.push(format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")));
+        lines.push(format!(
+            "DTEND;VALUE=DATE:{}",
+            (date + Duration::days(1)).format("%Y%m%d")
+        ));
+        lines.push(format!("SUMMARY:{}", escape(&summary)));
+        lines.push(format!("DESCRIPTION:{}", escape(&description)));
+        lines.push("END:VEVENT".to_owned());
+    }
+
+    lines.push("END:VCALENDAR".to_owned());
+
+    Ok(lines.iter().map(|line| fold(line) + "\r\n").collect())
+}
+
+/// Escapes text values as required by iCalendar
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Splits lines longer than allowed into continuation lines (without splitting characters)
+fn fold(line: &str) -> String {
+    let mut folded = String::new();
+    let mut length = 0;
+
+    for c in line.chars() {
+        if length + c.len_utf8() > MAX_LINE_LENGTH {
+            // Continuation lines start with a space, which counts towards their length
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+
+        folded.push(c);
+        length += c.len_utf8();
+    }
+
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::{profile::Profile, types::Rarity};
+
+    /// A planned card, whose name is used for the summary and the description
+    fn planned(name: &str) -> CardEntry {
+        let mut cards = vec![CardEntry {
+            name: name.to_owned(),
+            rarity: Rarity::Common,
+            level: 9,
+            ..CardEntry::new()
+        }];
+
+        CardEntry::compute_all(&mut cards, &Profile::default()).unwrap();
+        CardEntry::sum_all(&mut cards).unwrap();
+
+        cards.remove(0)
+    }
+
+    /// Unfolds the lines of a calendar, returning the value of the first line with a given name
+    fn value_of(calendar: &str, name: &str) -> String {
+        let unfolded = calendar.replace("\r\n ", "");
+
+        unfolded
+            .split("\r\n")
+            .find_map(|line| line.strip_prefix(name))
+            .unwrap()
+            .to_owned()
+    }
+
+    #[test]
+    fn escapes_text_values() {
+        let card = planned("Knight; Archers, Goblins\\");
+        let calendar = to_ical(&[card], |card, _, _| {
+            Ok((card.name.clone(), "1\n2".to_owned()))
+        })
+        .unwrap();
+
+        assert_eq!(
+            value_of(&calendar, "SUMMARY:"),
+            "Knight\\; Archers\\, Goblins\\\\"
+        );
+        assert_eq!(value_of(&calendar, "DESCRIPTION:"), "1\\n2");
+    }
+
+    #[test]
+    fn folds_long_lines_without_splitting_characters() {
+        // The "ö" would end after the 75th byte of the first line
+        let name = format!("{}ö; {}", "a".repeat(66), "Königliche Rekruten ".repeat(8));
+        let card = planned(&name);
+        let calendar =
+            to_ical(&[card], |card, _, _| Ok((card.name.clone(), String::new()))).unwrap();
+
+        for line in calendar.split("\r\n") {
+            assert!(line.len() <= MAX_LINE_LENGTH, "{:?} is too long", line);
+        }
+
+        let lines = calendar.split("\r\n").collect::<Vec<_>>();
+        let summary = lines
+            .iter()
+            .position(|line| line.starts_with("SUMMARY:"))
+            .unwrap();
+        assert_eq!(lines[summary], format!("SUMMARY:{}", "a".repeat(66)));
+        assert!(lines[summary + 1].starts_with(" ö\\;"));
+
+        // Unfolding restores the (escaped) summary
+        assert_eq!(value_of(&calendar, "SUMMARY:"), escape(&name));
+    }
+
+    #[test]
+    fn describes_the_events_on_their_dates() {
+        let card = planned("Knight");
+        let date = card.computed.as_ref().unwrap().done_in_order_on.unwrap();
+
+        let calendar =
+            to_ical(&[card], |_, _, date| Ok((String::new(), date.to_string()))).unwrap();
+
+        let day = date.date_naive();
+        assert_eq!(
+            value_of(&calendar, "DTSTART;VALUE=DATE:"),
+            day.format("%Y%m%d").to_string()
+        );
+        assert_eq!(value_of(&calendar, "DESCRIPTION:"), day.to_string());
+    }
+}
@@ -0,0 +1,169 @@
+/*!
+The cards of the game, which the cards entered by the user get matched with (by their names)
+
+Each card of the catalog has an icon in `static/cards` (see `icon_path`).
+*/
+
+use super::types::{CardEntry, CardType, Rarity};
+
+/// A card of the game
+#[derive(Debug, PartialEq, Clone)]
+pub struct CatalogCard {
+    pub name: &'static str,
+    pub rarity: Rarity,
+    pub card_type: CardType,
+}
+
+const fn card(name: &'static str, rarity: Rarity, card_type: CardType) -> CatalogCard {
+    CatalogCard {
+        name,
+        rarity,
+        card_type,
+    }
+}
+
+/// All cards of the game (at the time of writing)
+pub const CATALOG: &[CatalogCard] = &[
+    // Common cards
+    card("Knight", Rarity::Common, CardType::Troop),
+    card("Archers", Rarity::Common, CardType::Troop),
+    card("Bomber", Rarity::Common, CardType::Troop),
+    card("Barbarians", Rarity::Common, CardType::Troop),
+    card("Minions", Rarity::Common, CardType::Troop),
+    card("Minion Horde", Rarity::Common, CardType::Troop),
+    card("Goblins", Rarity::Common, CardType::Troop),
+    card("Spear Goblins", Rarity::Common, CardType::Troop),
+    card("Skeletons", Rarity::Common, CardType::Troop),
+    card("Bats", Rarity::Common, CardType::Troop),
+    card("Fire Spirit", Rarity::Common, CardType::Troop),
+    card("Ice Spirit", Rarity::Common, CardType::Troop),
+    card("Electro Spirit", Rarity::Common, CardType::Troop),
+    card("Royal Giant", Rarity::Common, CardType::Troop),
+    card("Elite Barbarians", Rarity::Common, CardType::Troop),
+    card("Firecracker", Rarity::Common, CardType::Troop),
+    card("Rascals", Rarity::Common, CardType::Troop),
+    card("Royal Recruits", Rarity::Common, CardType::Troop),
+    card("Skeleton Barrel", Rarity::Common, CardType::Troop),
+    card("Goblin Gang", Rarity::Common, CardType::Troop),
+    card("Skeleton Dragons", Rarity::Common, CardType::Troop),
+    card("Arrows", Rarity::Common, CardType::Spell),
+    card("Zap", Rarity::Common, CardType::Spell),
+    card("Giant Snowball", Rarity::Common, CardType::Spell),
+    card("Royal Delivery", Rarity::Common, CardType::Spell),
+    card("Cannon", Rarity::Common, CardType::Building),
+    card("Mortar", Rarity::Common, CardType::Building),
+    card("Tesla", Rarity::Common, CardType::Building),
+    // Rare cards
+    card("Giant", Rarity::Rare, CardType::Troop),
+    card("Musketeer", Rarity::Rare, CardType::Troop),
+    card("Mini P.E.K.K.A", Rarity::Rare, CardType::Troop),
+    card("Valkyrie", Rarity::Rare, CardType::Troop),
+    card("Hog Rider", Rarity::Rare, CardType::Troop),
+    card("Wizard", Rarity::Rare, CardType::Troop),
+    card("Three Musketeers", Rarity::Rare, CardType::Troop),
+    card("Ice Golem", Rarity::Rare, CardType::Troop),
+    card("Mega Minion", Rarity::Rare, CardType::Troop),
+    card("Dart Goblin", Rarity::Rare, CardType::Troop),
+    card("Battle Ram", Rarity::Rare, CardType::Troop),
+    card("Zappies", Rarity::Rare, CardType::Troop),
+    card("Flying Machine", Rarity::Rare, CardType::Troop),
+    card("Royal Hogs", Rarity::Rare, CardType::Troop),
+    card("Battle Healer", Rarity::Rare, CardType::Troop),
+    card("Elixir Golem", Rarity::Rare, CardType::Troop),
+    card("Heal Spirit", Rarity::Rare, CardType::Troop),
+    card("Fireball", Rarity::Rare, CardType::Spell),
+    card("Rocket", Rarity::Rare, CardType::Spell),
+    card("Earthquake", Rarity::Rare, CardType::Spell),
+    card("Goblin Hut", Rarity::Rare, CardType::Building),
+    card("Bomb Tower", Rarity::Rare, CardType::Building),
+    card("Inferno Tower", Rarity::Rare, CardType::Building),
+    card("Elixir Collector", Rarity::Rare, CardType::Building),
+    card("Furnace", Rarity::Rare, CardType::Building),
+    card("Barbarian Hut", Rarity::Rare, CardType::Building),
+    card("Tombstone", Rarity::Rare, CardType::Building),
+    card("Goblin Cage", Rarity::Rare, CardType::Building),
+    // Epic cards
+    card("Prince", Rarity::Epic, CardType::Troop),
+    card("Baby Dragon", Rarity::Epic, CardType::Troop),
+    card("Skeleton Army", Rarity::Epic, CardType::Troop),
+    card("Witch", Rarity::Epic, CardType::Troop),
+    card("P.E.K.K.A", Rarity::Epic, CardType::Troop),
+    card("Balloon", Rarity::Epic, CardType::Troop),
+    card("Giant Skeleton", Rarity::Epic, CardType::Troop),
+    card("Golem", Rarity::Epic, CardType::Troop),
+    card("Dark Prince", Rarity::Epic, CardType::Troop),
+    card("Bowler", Rarity::Epic, CardType::Troop),
+    card("Executioner", Rarity::Epic, CardType::Troop),
+    card("Cannon Cart", Rarity::Epic, CardType::Troop),
+    card("Guards", Rarity::Epic, CardType::Troop),
+    card("Goblin Giant", Rarity::Epic, CardType::Troop),
+    card("Electro Dragon", Rarity::Epic, CardType::Troop),
+    card("Hunter", Rarity::Epic, CardType::Troop),
+    card("Wall Breakers", Rarity::Epic, CardType::Troop),
+    card("Electro Giant", Rarity::Epic, CardType::Troop),
+    card("Mirror", Rarity::Epic, CardType::Spell),
+    card("Lightning", Rarity::Epic, CardType::Spell),
+    card("Rage", Rarity::Epic, CardType::Spell),
+    card("Freeze", Rarity::Epic, CardType::Spell),
+    card("Poison", Rarity::Epic, CardType::Spell),
+    card("Tornado", Rarity::Epic, CardType::Spell),
+    card("Clone", Rarity::Epic, CardType::Spell),
+    card("Barbarian Barrel", Rarity::Epic, CardType::Spell),
+    card("Goblin Barrel", Rarity::Epic, CardType::Spell),
+    card("X-Bow", Rarity::Epic, CardType::Building),
+    card("Goblin Drill", Rarity::Epic, CardType::Building),
+    // Legendary cards
+    card("Ice Wizard", Rarity::Legendary, CardType::Troop),
+    card("Princess", Rarity::Legendary, CardType::Troop),
+    card("Miner", Rarity::Legendary, CardType::Troop),
+    card("Sparky", Rarity::Legendary, CardType::Troop),
+    card("Lava Hound", Rarity::Legendary, CardType::Troop),
+    card("Lumberjack", Rarity::Legendary, CardType::Troop),
+    card("Inferno Dragon", Rarity::Legendary, CardType::Troop),
+    card("Electro Wizard", Rarity::Legendary, CardType::Troop),
+    card("Night Witch", Rarity::Legendary, CardType::Troop),
+    card("Bandit", Rarity::Legendary, CardType::Troop),
+    card("Royal Ghost", Rarity::Legendary, CardType::Troop),
+    card("Magic Archer", Rarity::Legendary, CardType::Troop),
+    card("Ram Rider", Rarity::Legendary, CardType::Troop),
+    card("Fisherman", Rarity::Legendary, CardType::Troop),
+    card("Mega Knight", Rarity::Legendary, CardType::Troop),
+    card("Mother Witch", Rarity::Legendary, CardType::Troop),
+    card("Graveyard", Rarity::Legendary, CardType::Spell),
+    card("The Log", Rarity::Legendary, CardType::Spell),
+];
+
+impl CatalogCard {
+    /// Returns the file name of the card's icon (without its extension)
+    pub fn slug(&self) -> String {
+        slug(self.name)
+    }
+}
+
+/// Simplifies a name for matching (like `mini-pekka` for `Mini P.E.K.K.A`)
+pub fn slug(name: &str) -> String {
+    name.to_lowercase()
+        .replace('.', "")
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Finds the card of the catalog with a name (ignoring the case and punctuation)
+pub fn find(name: &str) -> Option<&'static CatalogCard> {
+    let slug = slug(name);
+
+    CATALOG.iter().find(|card| card.slug() == slug)
+}
+
+/// Returns the path of a card's icon, falling back to a generic icon of its type
+pub fn icon_path(card: &CardEntry) -> String {
+    match find(&card.name) {
+        Some(catalog_card) => format!("cards/{}.svg", catalog_card.slug()),
+        None => format!(
+            "cards/{}.svg",
+            format!("{:?}", card.card_type).to_lowercase()
+        ),
+    }
+}
@@ -0,0 +1,308 @@
+use super::{error::Result, types::CardEntry};
+use std::fmt::Write;
+
+/// The width of each chart
+const WIDTH: f64 = 600.;
+
+/// The space left of the timeline for the names of the cards
+const LABEL_WIDTH: f64 = 150.;
+
+/// The height of each card in the timeline
+const ROW_HEIGHT: f64 = 18.;
+
+/// The height of the gold curve (without the axes)
+const CURVE_HEIGHT: f64 = 200.;
+
+/// The space around the plotted area
+const MARGIN: f64 = 20.;
+
+/// The amount of days without upgrades which gets highlighted in the timeline
+pub const LONG_GAP_DAYS: f64 = 14.;
+
+/// The texts written into the charts (e.g. in the language of the user interface)
+pub trait ChartLabels {
+    /// Labels the day of an upgrade in the timeline
+    fn days(&self, days: f64) -> String;
+
+    /// Labels the total gold on the gold curve
+    fn gold(&self, gold: usize) -> String;
+
+    /// Labels the first day on the axis
+    fn today(&self) -> String;
+
+    /// Labels the last day on the axis
+    fn in_days(&self, days: f64) -> String;
+}
+
+/// A card upgrade planned by `CardEntry::sum_all`
+struct Upgrade<'a> {
+    card: &'a CardEntry,
+    days: f64,
+}
+
+/// Returns the planned upgrades in chronological order
+fn planned_upgrades(cards: &[CardEntry]) -> Vec<Upgrade<'_>> {
+    let mut upgrades = cards
+        .iter()
+        .filter_map(|card| {
+            card.computed
+                .as_ref()
+                .and_then(|data| data.days_in_order)
+                .map(|days| Upgrade { card, days })
+        })
+        .collect::<Vec<_>>();
+
+    upgrades.sort_by(|a, b| a.days.total_cmp(&b.days));
+
+    upgrades
+}
+
+/**
+Plots the day each card reaches its next level (using `days_in_order`) as an SVG image
+
+Stretches of at least `LONG_GAP_DAYS` without any upgrades are highlighted.
+*/
+pub fn timeline_svg(cards: &[CardEntry], labels: &dyn ChartLabels) -> String {
+    let upgrades = planned_upgrades(cards);
+
+    let max_days = upgrades.last().map_or(0., |upgrade| upgrade.days).max(1.);
+    let x = |days: f64| LABEL_WIDTH + days / max_days * (WIDTH - LABEL_WIDTH - MARGIN);
+
+    let bottom = MARGIN + upgrades.len() as f64 * ROW_HEIGHT;
+    let mut svg = open_svg(bottom + 2. * MARGIN);
+
+    // Highlight long stretches without upgrades
+    let mut previous = 0.;
+
+    for upgrade in &upgrades {
+        if upgrade.days - previous >= LONG_GAP_DAYS {
+            let _ = write!(
+                svg,
+                r##"<rect x="{:.1}" y="{}" width="{:.1}" height="{}" fill="#fdd" />"##,
+                x(previous),
+                MARGIN,
+                x(upgrade.days) - x(previous),
+                bottom - MARGIN,
+            );
+        }
+
+        previous = upgrade.days;
+    }
+
+    write_day_grid(&mut svg, max_days, &x, MARGIN, bottom, labels);
+
+    // Place each card in its own row
+    for (i, upgrade) in upgrades.iter().enumerate() {
+        let y = MARGIN + (i as f64 + 0.5) * ROW_HEIGHT;
+
+        let _ = write!(
+            svg,
+            concat!(
+                r#"<text x="{:.1}" y="{:.1}" text-anchor="end" dominant-baseline="middle">{}</text>"#,
+                r#"<circle cx="{:.1}" cy="{:.1}" r="4"><title>{}</title></circle>"#,
+            ),
+            LABEL_WIDTH - 10.,
+            y,
+            escape(&upgrade.card.name),
+            x(upgrade.days),
+            y,
+            escape(&labels.days(upgrade.days.ceil())),
+        );
+    }
+
+    close_svg(svg)
+}
+
+/// Plots the gold needed for all upgrades until each day as an SVG image
+pub fn gold_curve_svg(cards: &[CardEntry], labels: &dyn ChartLabels) -> Result<String> {
+    let upgrades = planned_upgrades(cards);
+
+    let mut total_gold = 0;
+    for upgrade in &upgrades {
+        total_gold += upgrade.card.get_needed_gold()?;
+    }
+
+    let max_days = upgrades.last().map_or(0., |upgrade| upgrade.days).max(1.);
+    let x = |days: f64| LABEL_WIDTH + days / max_days * (WIDTH - LABEL_WIDTH - MARGIN);
+
+    let bottom = MARGIN + CURVE_HEIGHT;
+    let y = |gold: usize| bottom - gold as f64 / total_gold.max(1) as f64 * CURVE_HEIGHT;
+
+    let mut svg = open_svg(bottom + 2. * MARGIN);
+
+    write_day_grid(&mut svg, max_days, &x, MARGIN, bottom, labels);
+
+    let _ = write!(
+        svg,
+        r#"<text x="{:.1}" y="{:.1}" text-anchor="end" dominant-baseline="middle">{}</text>"#,
+        LABEL_WIDTH - 10.,
+        y(total_gold),
+        escape(&labels.gold(total_gold)),
+    );
+
+    // Step up at each upgrade
+    let mut gold = 0;
+    let mut points = format!("{:.1},{:.1}", x(0.), y(0));
+
+    for upgrade in &upgrades {
+        let _ = write!(points, " {:.1},{:.1}", x(upgrade.days), y(gold));
+        gold += upgrade.card.get_needed_gold()?;
+        let _ = write!(points, " {:.1},{:.1}", x(upgrade.days), y(gold));
+    }
+
+    let _ = write!(
+        svg,
+        r#"<polyline points="{}" fill="none" stroke="goldenrod" stroke-width="2" />"#,
+        points,
+    );
+
+    Ok(close_svg(svg))
+}
+
+fn open_svg(height: f64) -> String {
+    format!(
+        concat!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h:.0}" "#,
+            r#"viewBox="0 0 {w} {h:.0}" font-family="sans-serif" font-size="12">"#,
+        ),
+        w = WIDTH,
+        h = height,
+    )
+}
+
+fn close_svg(mut svg: String) -> String {
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Draws a vertical line for every few weeks, labeling the last day on the axis
+fn write_day_grid(
+    svg: &mut String,
+    max_days: f64,
+    x: &dyn Fn(f64) -> f64,
+    top: f64,
+    bottom: f64,
+    labels: &dyn ChartLabels,
+) {
+    // Keep the amount of lines readable
+    let step = 7. * (max_days / 7. / 20.).ceil().max(1.);
+
+    let mut days = 0.;
+    while days <= max_days {
+        let _ = write!(
+            svg,
+            r##"<line x1="{x:.1}" y1="{}" x2="{x:.1}" y2="{}" stroke="#ddd" />"##,
+            top,
+            bottom,
+            x = x(days),
+        );
+
+        days += step;
+    }
+
+    let _ = write!(
+        svg,
+        concat!(
+            r#"<text x="{:.1}" y="{:.1}">{}</text>"#,
+            r#"<text x="{:.1}" y="{:.1}" text-anchor="end">{}</text>"#,
+        ),
+        x(0.),
+        bottom + MARGIN,
+        escape(&labels.today()),
+        x(max_days),
+        bottom + MARGIN,
+        escape(&labels.in_days(max_days.ceil())),
+    );
+}
+
+/// Escapes text for use in SVG
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::{profile::Profile, types::Rarity};
+
+    /// Writes the same text for every label, which needs escaping
+    struct Labels;
+
+    impl ChartLabels for Labels {
+        fn days(&self, _: f64) -> String {
+            "<days>".to_owned()
+        }
+
+        fn gold(&self, _: usize) -> String {
+            "Gold & more".to_owned()
+        }
+
+        fn today(&self) -> String {
+            "\"today\"".to_owned()
+        }
+
+        fn in_days(&self, _: f64) -> String {
+            "later".to_owned()
+        }
+    }
+
+    /// A card planned to be upgraded after a given amount of days
+    fn planned(name: &str, days: f64) -> CardEntry {
+        let mut card = CardEntry {
+            name: name.to_owned(),
+            rarity: Rarity::Common,
+            level: 9,
+            ..CardEntry::new()
+        };
+
+        let mut data = card.calc_remaining(&Profile::default()).unwrap().unwrap();
+        data.days_in_order = Some(days);
+        card.computed = Some(data);
+
+        card
+    }
+
+    fn highlights(svg: &str) -> usize {
+        svg.matches(r##"fill="#fdd""##).count()
+    }
+
+    #[test]
+    fn highlights_long_gaps_between_upgrades() {
+        let cards = vec![
+            planned("Knight", 3.),
+            planned("Archers", 3. + LONG_GAP_DAYS),
+            planned("Goblins", 4. + LONG_GAP_DAYS),
+        ];
+
+        assert_eq!(highlights(&timeline_svg(&cards, &Labels)), 1);
+    }
+
+    #[test]
+    fn highlights_a_long_gap_from_today_on() {
+        let starts_late = vec![planned("Knight", LONG_GAP_DAYS)];
+        let almost = vec![
+            planned("Knight", LONG_GAP_DAYS - 0.5),
+            planned("Archers", 2. * LONG_GAP_DAYS - 1.),
+        ];
+
+        assert_eq!(highlights(&timeline_svg(&starts_late, &Labels)), 1);
+        assert_eq!(highlights(&timeline_svg(&almost, &Labels)), 0);
+    }
+
+    #[test]
+    fn escapes_names_and_labels() {
+        let cards = vec![planned("<Knight> & \"Archers\"", 1.)];
+
+        let timeline = timeline_svg(&cards, &Labels);
+        assert!(timeline.contains("&lt;Knight&gt; &amp; &quot;Archers&quot;"));
+        assert!(timeline.contains("<title>&lt;days&gt;</title>"));
+        assert!(timeline.contains("&quot;today&quot;"));
+        assert!(!timeline.contains("<Knight>"));
+
+        let gold_curve = gold_curve_svg(&cards, &Labels).unwrap();
+        assert!(gold_curve.contains(">Gold &amp; more<"));
+    }
+}
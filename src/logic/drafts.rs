@@ -0,0 +1,123 @@
+use super::types::CardEntry;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// An unsaved edit of a card
+#[derive(Clone)]
+pub struct Draft {
+    /// The card as it was when the edit started
+    pub base: CardEntry,
+
+    /// The edited card
+    pub card: CardEntry,
+}
+
+/**
+Keeps the unsaved edits of cards by their UUIDs
+
+Unlike the state of a component, the drafts stay with their cards
+when the list gets re-sorted or re-rendered.
+*/
+#[derive(Default)]
+pub struct DraftStore {
+    drafts: HashMap<Uuid, Draft>,
+}
+
+impl DraftStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the draft of a card (if it's being edited)
+    pub fn get(&self, uuid: &Uuid) -> Option<&Draft> {
+        self.drafts.get(uuid)
+    }
+
+    /// Stores an edit of a card, remembering the original card on the first edit
+    pub fn edit(&mut self, base: &CardEntry, card: CardEntry) {
+        self.drafts
+            .entry(card.uuid)
+            .and_modify(|draft| draft.card = card.clone())
+            .or_insert_with(|| Draft {
+                base: base.clone(),
+                card,
+            });
+    }
+
+    /// Removes the draft of a card (after saving or cancelling the edit)
+    pub fn discard(&mut self, uuid: &Uuid) {
+        self.drafts.remove(uuid);
+    }
+
+    /// Checks if a card was changed by something else since its edit started
+    pub fn has_conflict(&self, current: &CardEntry) -> bool {
+        self.drafts
+            .get(&current.uuid)
+            .is_some_and(|draft| !draft.base.same_values(current))
+    }
+
+    /// Removes the drafts of cards which don't exist anymore
+    pub fn retain_cards(&mut self, cards: &[CardEntry]) {
+        self.drafts
+            .retain(|uuid, _| cards.iter().any(|card| card.uuid == *uuid));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_base_of_the_first_edit() {
+        let card = CardEntry::new();
+        let mut drafts = DraftStore::new();
+
+        drafts.edit(
+            &card,
+            CardEntry {
+                level: 5,
+                ..card.clone()
+            },
+        );
+        drafts.edit(
+            &CardEntry {
+                level: 5,
+                ..card.clone()
+            },
+            CardEntry {
+                level: 6,
+                ..card.clone()
+            },
+        );
+
+        let draft = drafts.get(&card.uuid).unwrap();
+        assert!(draft.base.same_values(&card));
+        assert_eq!(draft.card.level, 6);
+    }
+
+    #[test]
+    fn detects_conflicting_drafts() {
+        let card = CardEntry::new();
+        let mut drafts = DraftStore::new();
+        drafts.edit(
+            &card,
+            CardEntry {
+                level: 5,
+                ..card.clone()
+            },
+        );
+
+        // Unchanged cards don't conflict with their drafts
+        assert!(!drafts.has_conflict(&card));
+
+        // The card changed elsewhere (e.g. in another tab) while being edited
+        assert!(drafts.has_conflict(&CardEntry {
+            have: 20,
+            ..card.clone()
+        }));
+
+        // Saving or cancelling the edit resolves the conflict
+        drafts.discard(&card.uuid);
+        assert!(!drafts.has_conflict(&CardEntry { have: 20, ..card }));
+    }
+}
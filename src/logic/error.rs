@@ -0,0 +1,71 @@
+use super::types::Rarity;
+use thiserror::Error;
+use uuid::Uuid;
+
+/// The errors which can occur in the logic of cr-tools
+#[derive(Error, Debug, PartialEq, Clone)]
+pub enum Error {
+    /// The level of a card is out of range for its rarity
+    #[error("Level {level} is invalid for {rarity:?} cards")]
+    InvalidLevel { level: usize, rarity: Rarity },
+
+    /// A predicted date is too far in the future to be represented
+    #[error("The predicted date is out of range")]
+    DateOverflow,
+
+    /// There is no card with the given UUID
+    #[error("No card with the UUID {0}")]
+    UnknownCard(Uuid),
+
+    /// The King level of the player doesn't allow upgrading a card to a level yet
+    #[error("Level {level} needs a higher King level than {king_level}")]
+    LevelCapped { level: usize, king_level: usize },
+
+    /// A card has fewer cards than are taken away from it
+    #[error("Cannot take away {amount} cards from the {have} available")]
+    NotEnoughCards { have: usize, amount: usize },
+
+    /// A line of entered text does not describe a card (see `parse_bulk`)
+    #[error("Line {line}: {problem:?}")]
+    InvalidLine { line: usize, problem: LineProblem },
+
+    /// Data of an older format could not be converted
+    #[error("Cannot migrate the data: {0}")]
+    MigrationFailed(String),
+
+    /// An event does not fit the event log (e.g. updating a deleted card)
+    #[error("Conflicting event: {0}")]
+    EventLogConflict(String),
+
+    /// The name of a profile can't be used for syncing (see `check_profile`)
+    #[error("Invalid profile name \"{0}\" (use up to 64 letters, digits, - and _)")]
+    InvalidProfile(String),
+}
+
+/// Why a line of entered text does not describe a card (see `Error::InvalidLine`)
+#[derive(Debug, PartialEq, Clone)]
+pub enum LineProblem {
+    /// The line doesn't contain 3 to 5 values, but the given amount
+    ValueCount(usize),
+
+    /// The name of the card is missing
+    EmptyName,
+
+    /// The level is not a whole number
+    LevelNotANumber(String),
+
+    /// The amount of cards is not a whole number
+    AmountNotANumber(String),
+
+    /// The rarity doesn't match any of `Rarity`
+    UnknownRarity(String),
+
+    /// The type doesn't match any of `CardType`
+    UnknownType(String),
+
+    /// The entered card is invalid (e.g. its level is out of range for its rarity)
+    InvalidCard(Box<Error>),
+}
+
+/// The result type of the logic of cr-tools
+pub type Result<T> = std::result::Result<T, Error>;
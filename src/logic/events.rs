@@ -1,22 +1,43 @@
-use super::types::{CardEntry, CardEntryV1};
-use anyhow::Result;
-use libocc::{Event, Projector};
-use serde::{Deserialize, Serialize};
+use super::{
+    error::{Error, Result},
+    transactions::CardTransaction,
+    types::{CardEntry, CardEntryV1},
+};
+use chrono::Utc;
+use libocc::{Event, Projector, Timestamp};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::HashSet;
+use uuid::Uuid;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct EventSourcingService {
     projector: Projector<CardEntry>,
+
+    /// The separate event log of the cards received and donated
+    #[serde(default = "Projector::new")]
+    transactions: Projector<CardTransaction>,
 }
 
+impl Default for EventSourcingService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(clippy::should_implement_trait)]
 impl EventSourcingService {
     pub fn new() -> Self {
         Self {
             projector: Projector::new(),
+            transactions: Projector::new(),
         }
     }
 
-    pub fn load(projector: Projector<CardEntry>) -> Self {
-        Self { projector }
+    pub fn load(projector: Projector<CardEntry>, transactions: Projector<CardTransaction>) -> Self {
+        Self {
+            projector,
+            transactions,
+        }
     }
 
     pub fn migrate_from_v1(old_cards: Vec<CardEntryV1>) -> Result<Self> {
@@ -24,11 +45,34 @@ impl EventSourcingService {
 
         // Add all cards
         for card in old_cards {
-            projector.push(Event::create(card.retrofit_uuid()))?;
+            projector
+                .push(Event::create(card.retrofit_uuid()))
+                .map_err(|e| Error::MigrationFailed(e.to_string()))?;
         }
 
         // Return the projector inside of a new EventSourcingService
-        Ok(Self { projector })
+        Ok(Self {
+            projector,
+            transactions: Projector::new(),
+        })
+    }
+
+    /// Pushes an event onto the event log, failing if it conflicts with the log
+    pub fn push(&mut self, event: Event<CardEntry>) -> Result<()> {
+        self.projector
+            .push(event)
+            .map_err(|e| Error::EventLogConflict(e.to_string()))
+    }
+
+    /// Records a transaction in its event log (without changing the card itself)
+    pub fn push_transaction(&mut self, transaction: CardTransaction) -> Result<()> {
+        self.transactions
+            .push(Event::create(transaction))
+            .map_err(|e| Error::EventLogConflict(e.to_string()))
+    }
+
+    pub fn borrow_transactions(&self) -> &Projector<CardTransaction> {
+        &self.transactions
     }
 
     pub fn borrow(&self) -> &Projector<CardEntry> {
@@ -39,3 +83,380 @@ impl EventSourcingService {
         &mut self.projector
     }
 }
+
+/// The result of merging another copy of the event logs into the local ones
+#[derive(Debug, Default)]
+pub struct MergeReport {
+    /// The amount of events only the other copy knew about
+    pub received: usize,
+
+    /// The amount of events only the local copy knew about
+    pub kept: usize,
+
+    /// The cards changed in both copies independently (the latest change wins)
+    pub conflicts: Vec<Uuid>,
+}
+
+impl MergeReport {
+    /// Checks if the other copy lacks some of the local events
+    pub fn is_ahead(&self) -> bool {
+        self.kept > 0
+    }
+}
+
+/// A deleted card, which can still be restored from the event log
+#[derive(Clone, PartialEq)]
+pub struct DeletedCard {
+    /// The card as it was when it got deleted
+    pub card: CardEntry,
+
+    pub deleted_on: Timestamp,
+}
+
+impl EventSourcingService {
+    /**
+    Lists the deleted cards which weren't restored since (most recently deleted first)
+
+    Deleting only records an event, so the event log works as a trash.
+    Restoring a card creates it again (with the same UUID).
+    */
+    pub fn trash(&self) -> Result<Vec<DeletedCard>> {
+        let mut trash: Vec<DeletedCard> = vec![];
+
+        for event in read_events(&self.projector)?
+            .segments
+            .into_iter()
+            .flat_map(|segment| segment.events)
+        {
+            let uuid = event.borrow().uuid;
+            trash.retain(|deleted| deleted.card.uuid != uuid);
+
+            if let Event::Delete(_) = event {
+                trash.push(DeletedCard {
+                    deleted_on: *event.get_time(),
+                    card: event.take(),
+                });
+            }
+        }
+
+        trash.reverse();
+        Ok(trash)
+    }
+}
+
+/// Mirrors the (private) layout of a serialized `Projector`
+/// (which is why libocc is pinned to an exact version, see the tests below)
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "T: Clone + PartialEq + Serialize + DeserializeOwned")]
+struct ProjectorData<T: Clone + PartialEq> {
+    segments: Vec<SegmentData<T>>,
+}
+
+/// Mirrors the (private) layout of a serialized `Segment`
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "T: Clone + PartialEq + Serialize + DeserializeOwned")]
+struct SegmentData<T: Clone + PartialEq> {
+    timestamp: Timestamp,
+    snapshot: Vec<T>,
+    events: Vec<Event<T>>,
+}
+
+impl EventSourcingService {
+    /**
+    Merges another copy of the event logs (e.g. saved by another tab) into the local ones
+
+    Events known to both copies are kept once, all others get replayed in the order of their timestamps.
+    Events which can't be applied anymore (like updating a card deleted in the other copy) are dropped
+    and their cards get reported as conflicts.
+    */
+    pub fn merge(
+        &mut self,
+        cards: &Projector<CardEntry>,
+        transactions: &Projector<CardTransaction>,
+    ) -> Result<MergeReport> {
+        let mut report = MergeReport::default();
+
+        let (projector, card_report) = merge_log(&self.projector, cards, |card| card.uuid)?;
+        let (transactions, transaction_report) =
+            merge_log(&self.transactions, transactions, |transaction| {
+                transaction.card
+            })?;
+
+        report.received = card_report.received + transaction_report.received;
+        report.kept = card_report.kept + transaction_report.kept;
+        report.conflicts = card_report.conflicts;
+
+        self.projector = projector;
+        self.transactions = transactions;
+
+        Ok(report)
+    }
+}
+
+/**
+Reads all events of an event log (in the order they were logged)
+
+libocc offers no way to read the logged events, so they are taken from the serialized log.
+*/
+fn read_events<T>(log: &Projector<T>) -> Result<ProjectorData<T>>
+where
+    T: Clone + PartialEq + Serialize + DeserializeOwned,
+{
+    serde_json::to_value(log)
+        .and_then(serde_json::from_value)
+        .map_err(|e| Error::EventLogConflict(e.to_string()))
+}
+
+/// Merges two copies of an event log, identifying the entities by a UUID
+fn merge_log<T>(
+    local: &Projector<T>,
+    remote: &Projector<T>,
+    uuid_of: impl Fn(&T) -> Uuid,
+) -> Result<(Projector<T>, MergeReport)>
+where
+    T: Clone + PartialEq + Serialize + DeserializeOwned,
+{
+    let local = read_events(local)?;
+    let remote = read_events(remote)?;
+
+    // Start the merged log where the older copy starts
+    let start = local
+        .segments
+        .iter()
+        .chain(remote.segments.iter())
+        .map(|segment| segment.timestamp)
+        .min()
+        .unwrap_or_else(Utc::now);
+
+    let local_events = local
+        .segments
+        .into_iter()
+        .flat_map(|segment| segment.events)
+        .collect::<Vec<_>>();
+    let remote_events = remote
+        .segments
+        .into_iter()
+        .flat_map(|segment| segment.events)
+        .collect::<Vec<_>>();
+
+    // Events are identified by their time and their entity
+    let key_of = |event: &Event<T>| (*event.get_time(), uuid_of(event.borrow()));
+    let local_keys = local_events.iter().map(key_of).collect::<HashSet<_>>();
+    let remote_keys = remote_events.iter().map(key_of).collect::<HashSet<_>>();
+
+    let local_only = local_events
+        .iter()
+        .filter(|event| !remote_keys.contains(&key_of(event)))
+        .collect::<Vec<_>>();
+    let remote_only = remote_events
+        .iter()
+        .filter(|event| !local_keys.contains(&key_of(event)))
+        .collect::<Vec<_>>();
+
+    // Entities changed in both copies since they diverged
+    let remote_uuids = remote_only
+        .iter()
+        .map(|event| uuid_of(event.borrow()))
+        .collect::<HashSet<_>>();
+    let mut conflicts = local_only
+        .iter()
+        .map(|event| uuid_of(event.borrow()))
+        .filter(|uuid| remote_uuids.contains(uuid))
+        .collect::<Vec<_>>();
+
+    let mut report = MergeReport {
+        received: remote_only.len(),
+        kept: local_only.len(),
+        conflicts: vec![],
+    };
+
+    // All events in the order they happened (stable for equal timestamps)
+    let mut events = local_events.clone();
+    events.extend(remote_only.into_iter().cloned());
+    events.sort_by(|a, b| a.compare(b));
+
+    // Build an empty log starting before all events
+    let mut merged: Projector<T> = serde_json::to_value(ProjectorData::<T> {
+        segments: vec![SegmentData {
+            timestamp: start,
+            snapshot: vec![],
+            events: vec![],
+        }],
+    })
+    .and_then(serde_json::from_value)
+    .map_err(|e| Error::EventLogConflict(e.to_string()))?;
+
+    // Replay the events, dropping the ones which don't fit anymore
+    for event in events {
+        let uuid = uuid_of(event.borrow());
+
+        if merged.push(event).is_err() {
+            conflicts.push(uuid);
+        }
+    }
+
+    conflicts.sort();
+    conflicts.dedup();
+    report.conflicts = conflicts;
+
+    Ok((merged, report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(name: &str) -> CardEntry {
+        CardEntry {
+            name: name.to_owned(),
+            ..CardEntry::new()
+        }
+    }
+
+    #[test]
+    fn reads_the_events_of_libocc() {
+        let knight = card("Knight");
+        let mut log = Projector::new();
+        log.push(Event::create(knight.clone())).unwrap();
+        log.push(Event::update(card("Golem"))).unwrap_err();
+        log.push(Event::delete(knight.clone())).unwrap();
+
+        let data = read_events(&log).unwrap();
+        let events = data
+            .segments
+            .iter()
+            .flat_map(|segment| &segment.events)
+            .collect::<Vec<_>>();
+
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0], Event::Create(_)));
+        assert!(matches!(events[1], Event::Delete(_)));
+        assert_eq!(events[1].borrow().uuid, knight.uuid);
+    }
+
+    #[test]
+    fn round_trips_the_layout_of_libocc() {
+        let mut log = Projector::new();
+        log.push(Event::create(card("Knight"))).unwrap();
+        log.make_snapshot();
+        log.push(Event::create(card("Golem"))).unwrap();
+
+        // The mirrors read and write the same data as libocc itself
+        let data = read_events(&log).unwrap();
+        let round_trip: Projector<CardEntry> = serde_json::to_value(&data)
+            .and_then(serde_json::from_value)
+            .unwrap();
+
+        assert_eq!(data.segments.len(), 2);
+        assert_eq!(
+            serde_json::to_value(&round_trip).unwrap(),
+            serde_json::to_value(&log).unwrap()
+        );
+        assert!(round_trip.get_projection() == log.get_projection());
+    }
+
+    /// Loads a copy of the events with the given card events (and no transactions)
+    fn service(cards: &Projector<CardEntry>) -> EventSourcingService {
+        EventSourcingService::load(cards.clone(), Projector::new())
+    }
+
+    fn renamed(card: &CardEntry, name: &str) -> CardEntry {
+        CardEntry {
+            name: name.to_owned(),
+            ..card.clone()
+        }
+    }
+
+    #[test]
+    fn merges_overlapping_histories() {
+        let knight = card("Knight");
+        let mut shared = Projector::new();
+        shared.push(Event::create(knight.clone())).unwrap();
+
+        // Both copies continue the shared history independently
+        let mut local = service(&shared);
+        local.push(Event::create(card("Golem"))).unwrap();
+
+        let mut remote = shared.clone();
+        remote
+            .push(Event::update(renamed(&knight, "Sir Knight")))
+            .unwrap();
+
+        let report = local.merge(&remote, &Projector::new()).unwrap();
+
+        assert_eq!(report.received, 1);
+        assert_eq!(report.kept, 1);
+        assert!(report.conflicts.is_empty());
+
+        let mut names = local
+            .borrow()
+            .get_projection()
+            .iter()
+            .map(|card| card.name.clone())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["Golem", "Sir Knight"]);
+    }
+
+    #[test]
+    fn keeps_duplicate_events_once() {
+        let mut log = Projector::new();
+        log.push(Event::create(card("Knight"))).unwrap();
+        log.push(Event::create(card("Golem"))).unwrap();
+
+        let mut local = service(&log);
+
+        // Merging the same events (again) changes nothing
+        for _ in 0..2 {
+            let report = local.merge(&log, &Projector::new()).unwrap();
+
+            assert_eq!(report.received, 0);
+            assert_eq!(report.kept, 0);
+            assert!(report.conflicts.is_empty());
+        }
+
+        assert_eq!(
+            read_events(local.borrow()).unwrap().segments[0]
+                .events
+                .len(),
+            2
+        );
+        assert!(local.borrow().get_projection() == log.get_projection());
+    }
+
+    #[test]
+    fn reports_conflicting_changes() {
+        let knight = card("Knight");
+        let archers = card("Archers");
+        let mut shared = Projector::new();
+        shared.push(Event::create(knight.clone())).unwrap();
+        shared.push(Event::create(archers.clone())).unwrap();
+
+        // The remote copy renames the knight and deletes the archers
+        let mut remote = shared.clone();
+        remote
+            .push(Event::update(renamed(&knight, "Sir Knight")))
+            .unwrap();
+        remote.push(Event::delete(archers.clone())).unwrap();
+
+        // The local copy changes both afterwards
+        let mut local = service(&shared);
+        local
+            .push(Event::update(renamed(&knight, "Dark Knight")))
+            .unwrap();
+        local
+            .push(Event::update(renamed(&archers, "Royal Archers")))
+            .unwrap();
+
+        let report = local.merge(&remote, &Projector::new()).unwrap();
+
+        let mut conflicts = vec![knight.uuid, archers.uuid];
+        conflicts.sort();
+        assert_eq!(report.conflicts, conflicts);
+
+        // The latest change wins, and changes of deleted cards get dropped
+        let projection = local.borrow().get_projection();
+        assert_eq!(projection.len(), 1);
+        assert_eq!(projection[0].name, "Dark Knight");
+    }
+}
@@ -0,0 +1,211 @@
+use super::{
+    calc::{days_from_now, LevelCap},
+    error::Result,
+    types::{CardEntry, KING_LEVEL_XP, MAX_KING_LEVEL, MAX_LEVEL},
+};
+use chrono::{DateTime, Local};
+use serde_derive::{Deserialize, Serialize};
+
+/// The King level of a player and their progress towards the next one
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct KingProgress {
+    /// The current King level
+    pub level: usize,
+
+    /// The experience collected since reaching the current King level
+    pub experience: usize,
+
+    /// The experience expected from donations per week
+    pub donation_xp_per_week: usize,
+}
+
+impl Default for KingProgress {
+    /// Defaults to the highest King level, which has nothing left to project
+    fn default() -> Self {
+        Self {
+            level: MAX_KING_LEVEL,
+            experience: 0,
+            donation_xp_per_week: 0,
+        }
+    }
+}
+
+/// A predicted King level-up
+#[derive(Debug, PartialEq, Clone)]
+pub struct KingLevelUp {
+    /// The King level reached
+    pub level: usize,
+
+    /// The level cards can be upgraded to from then on
+    pub card_level_cap: usize,
+
+    /// The amount of days from now until the level is reached
+    pub days: f64,
+
+    /// The day the level is reached
+    pub date: DateTime<Local>,
+}
+
+/// Returns the highest level cards can be upgraded to at a given King level
+pub fn get_card_level_cap(king_level: usize) -> usize {
+    king_level.min(MAX_LEVEL)
+}
+
+/**
+Predicts the upcoming King levels of a player
+
+Uses the upgrades planned by `CardEntry::sum_all` (each card being upgraded once it's done
+in order) and the experience from donations, which is assumed to be collected steadily.
+Upgrades beyond the card level cap of the King level at the time are postponed
+until a level-up lifts the cap.
+*/
+pub fn project_king_levels(cards: &[CardEntry], king: &KingProgress) -> Result<Vec<KingLevelUp>> {
+    // The planned upgrades as (days, experience, next level), in chronological order
+    let mut upgrades = Vec::new();
+
+    for card in cards {
+        if card.level >= MAX_LEVEL {
+            continue;
+        }
+
+        if let Some(days) = card.computed.as_ref().and_then(|data| data.days_in_order) {
+            upgrades.push((days, card.get_upgrade_xp()? as f64, card.level + 1));
+        }
+    }
+
+    upgrades.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let donation_xp_per_day = king.donation_xp_per_week as f64 / 7.;
+
+    let mut level_ups = Vec::new();
+    let mut level = king.level.max(1);
+    let mut experience = king.experience as f64;
+    let mut now = 0.;
+
+    // The upgrades waiting for a higher King level as (experience, next level)
+    let mut postponed: Vec<(f64, usize)> = Vec::new();
+
+    // Handle each upgrade, followed by the donations after the last one
+    for upgrade in upgrades.into_iter().map(Some).chain(std::iter::once(None)) {
+        let until = upgrade.map_or(f64::INFINITY, |(days, _, _)| days);
+
+        // Level up using the donations collected until the upgrade
+        while level < MAX_KING_LEVEL {
+            let missing = KING_LEVEL_XP[level - 1] as f64 - experience;
+
+            let reached = if missing <= 0. {
+                now
+            } else if donation_xp_per_day > 0. {
+                now + missing / donation_xp_per_day
+            } else {
+                break;
+            };
+
+            if reached > until {
+                break;
+            }
+
+            experience += (reached - now) * donation_xp_per_day - KING_LEVEL_XP[level - 1] as f64;
+            now = reached;
+            level += 1;
+
+            level_ups.push(KingLevelUp {
+                level,
+                card_level_cap: get_card_level_cap(level),
+                days: now,
+                date: days_from_now(now)?,
+            });
+
+            // Make the upgrades allowed by the new level
+            postponed.retain(|&(xp, next_level)| {
+                let allowed = next_level <= get_card_level_cap(level);
+
+                if allowed {
+                    experience += xp;
+                }
+
+                !allowed
+            });
+        }
+
+        // Apply the upgrade, unless the King level doesn't allow it yet
+        if let Some((days, xp, next_level)) = upgrade {
+            experience += (days - now) * donation_xp_per_day;
+            now = days;
+
+            if next_level <= get_card_level_cap(level) {
+                experience += xp;
+            } else {
+                postponed.push((xp, next_level));
+            }
+        }
+    }
+
+    Ok(level_ups)
+}
+
+/// Finds the projected King level-up which allows upgrading a capped card to a given level
+pub fn lift_level_cap(next_level: usize, projection: &[KingLevelUp]) -> LevelCap {
+    projection
+        .iter()
+        .find(|level_up| level_up.card_level_cap >= next_level)
+        .map_or(LevelCap::Blocked, |level_up| {
+            LevelCap::LiftedOn(level_up.date)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::calc::CardData;
+
+    /// A card planned to be upgraded in the given amount of days
+    fn planned(level: usize, days: f64) -> CardEntry {
+        CardEntry {
+            level,
+            computed: Some(CardData {
+                cards_remaining: 0,
+                requests_remaining: 0,
+                weeks_remaining: days / 7.,
+                days_remaining: days,
+                done_on: Local::now(),
+                days_in_order: Some(days),
+                done_in_order_on: None,
+                level_cap: LevelCap::Allowed,
+                progress: 1.,
+                ready_to_upgrade: false,
+            }),
+            ..CardEntry::new()
+        }
+    }
+
+    fn king(level: usize) -> KingProgress {
+        KingProgress {
+            level,
+            experience: 0,
+            donation_xp_per_week: 7000,
+        }
+    }
+
+    #[test]
+    fn counts_upgrades_within_the_cap() {
+        let level_ups = project_king_levels(&[planned(11, 1.)], &king(12)).unwrap();
+
+        // 1000 XP from donations plus 800 XP from the upgrade leave 5200 of 7000 XP missing
+        assert_eq!(level_ups[0].level, 13);
+        assert!((level_ups[0].days - 6.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn postpones_upgrades_beyond_the_cap() {
+        let level_ups = project_king_levels(&[planned(12, 1.)], &king(12)).unwrap();
+
+        // The upgrade to level 13 waits for King level 13, which only donations reach
+        assert_eq!(level_ups[0].level, 13);
+        assert!((level_ups[0].days - 7.).abs() < 1e-9);
+
+        // Its 1600 XP count from then on
+        assert_eq!(level_ups[1].level, 14);
+        assert!((level_ups[1].days - 13.4).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,193 @@
+use super::types::{Arena, Rarity};
+use serde_derive::{Deserialize, Serialize};
+
+/**
+The expected amount of cards of a single card per week, by rarity
+
+Cards from chests, offers and rewards are spread over the whole card pool,
+so these are the shares one specific card can expect on average.
+*/
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Default)]
+pub struct CardsPerWeek {
+    pub common: f64,
+    pub rare: f64,
+    pub epic: f64,
+    pub legendary: f64,
+}
+
+impl CardsPerWeek {
+    /// Returns the expected cards per week for a given rarity
+    pub fn get(&self, rarity: &Rarity) -> f64 {
+        match rarity {
+            Rarity::Common => self.common,
+            Rarity::Rare => self.rare,
+            Rarity::Epic => self.epic,
+            Rarity::Legendary => self.legendary,
+        }
+    }
+
+    /// Adds the cards per week of another source
+    pub fn add(&mut self, other: &Self) {
+        self.common += other.common;
+        self.rare += other.rare;
+        self.epic += other.epic;
+        self.legendary += other.legendary;
+    }
+}
+
+/**
+The sources of cards besides requests to take into account
+
+All sources are disabled by default, which only counts requests.
+*/
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Default)]
+pub struct IncomeModel {
+    /// Cards from the chest cycle (depends on the arena)
+    pub chests: bool,
+
+    /// Cards bought from the daily shop offers
+    pub shop: bool,
+
+    /// Cards from the Pass Royale reward path
+    pub pass_royale: bool,
+
+    /// Cards from challenge rewards
+    pub challenges: bool,
+
+    /// The expected cards per week of each source
+    #[serde(default)]
+    pub rates: IncomeRates,
+}
+
+impl IncomeModel {
+    /// Returns the expected cards per week of all enabled sources combined
+    pub fn cards_per_week(&self, arena: &Arena) -> CardsPerWeek {
+        let mut total = CardsPerWeek::default();
+
+        if self.chests {
+            if let Some(tier) = get_chest_tier(arena) {
+                total.add(&self.rates.chests[tier]);
+            }
+        }
+
+        if self.shop {
+            total.add(&self.rates.shop);
+        }
+
+        if self.pass_royale {
+            total.add(&self.rates.pass_royale);
+        }
+
+        if self.challenges {
+            total.add(&self.rates.challenges);
+        }
+
+        total
+    }
+}
+
+/// The expected cards per week of a single card from each source of the income model
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct IncomeRates {
+    /// The chest cycle, by the tier of the arena (see `get_chest_tier`)
+    pub chests: [CardsPerWeek; 4],
+
+    /// The daily shop offers
+    pub shop: CardsPerWeek,
+
+    /// The Pass Royale reward path
+    pub pass_royale: CardsPerWeek,
+
+    /// The challenge rewards
+    pub challenges: CardsPerWeek,
+}
+
+impl Default for IncomeRates {
+    fn default() -> Self {
+        INCOME_RATES
+    }
+}
+
+/**
+Returns the tier of the chest cycle in a given arena (if it has any)
+
+Chests contain more cards in higher arenas, so the arenas are grouped by the size of their chests.
+*/
+pub fn get_chest_tier(arena: &Arena) -> Option<usize> {
+    match arena {
+        Arena::TrainingCamp => None,
+        Arena::GoblinStadium | Arena::BonePit | Arena::BarbarianBowl => Some(0),
+        Arena::PekkasPlayhouse | Arena::SpellValley | Arena::BuildersWorkshop => Some(1),
+        Arena::RoyalArena | Arena::FrozenPeak | Arena::JungleArena => Some(2),
+        Arena::HogMountain
+        | Arena::ElectroValley
+        | Arena::SpookyTown
+        | Arena::RascalsHideout
+        | Arena::SerenityPeak
+        | Arena::MinersMine
+        | Arena::ExecutionersKitchen
+        | Arena::RoyalCrypt
+        | Arena::SilentSanctuary
+        | Arena::DragonSpa
+        | Arena::LegendaryArena => Some(3),
+    }
+}
+
+/**
+The default expected cards per week of each source
+
+These are rough estimates rather than published drop rates:
+
+- The chests of each tier contain about as many cards as the first tier times the tier
+- The shop offers about as many cards as a chest of the first tier
+- The Pass Royale rewards about twice that, with more epic and legendary cards
+- The challenges reward about half of it
+
+They are saved with the profile (like `REQUEST_FREQUENCY`), so they can be adjusted.
+*/
+pub const INCOME_RATES: IncomeRates = IncomeRates {
+    chests: [
+        CardsPerWeek {
+            common: 1.,
+            rare: 0.2,
+            epic: 0.02,
+            legendary: 0.,
+        },
+        CardsPerWeek {
+            common: 2.,
+            rare: 0.4,
+            epic: 0.04,
+            legendary: 0.002,
+        },
+        CardsPerWeek {
+            common: 3.,
+            rare: 0.6,
+            epic: 0.06,
+            legendary: 0.004,
+        },
+        CardsPerWeek {
+            common: 4.,
+            rare: 0.8,
+            epic: 0.08,
+            legendary: 0.006,
+        },
+    ],
+    shop: CardsPerWeek {
+        common: 1.,
+        rare: 0.2,
+        epic: 0.02,
+        legendary: 0.,
+    },
+    pass_royale: CardsPerWeek {
+        common: 2.,
+        rare: 0.4,
+        epic: 0.05,
+        legendary: 0.01,
+    },
+    challenges: CardsPerWeek {
+        common: 0.5,
+        rare: 0.1,
+        epic: 0.01,
+        legendary: 0.,
+    },
+};
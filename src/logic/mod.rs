@@ -1,3 +1,19 @@
+pub mod bulk;
 pub mod calc;
+pub mod calendar;
+pub mod catalog;
+pub mod charts;
+pub mod drafts;
+pub mod error;
 pub mod events;
+pub mod experience;
+pub mod income;
+pub mod planner;
+pub mod profile;
+pub mod recommendation;
+pub mod scenario;
+pub mod settings;
+pub mod stats;
+pub mod sync;
+pub mod transactions;
 pub mod types;
@@ -0,0 +1,304 @@
+use super::{
+    calc::LevelCap,
+    error::{Error, Result},
+    experience::{lift_level_cap, project_king_levels, KingLevelUp},
+    profile::Profile,
+    types::CardEntry,
+};
+use std::{cell::OnceCell, cmp};
+use uuid::Uuid;
+
+/**
+Keeps a list of cards sorted by their remaining time, including their in-order values
+
+Unlike re-sorting and re-summing the whole list on every change, the planner
+only computes the values of the affected card, moves it to its new position
+and continues the in-order sums from there.
+
+The King level projection depends on the whole upgrade plan, so it's only
+made once it's needed after a change.
+*/
+pub struct Planner {
+    cards: Vec<CardEntry>,
+    profile: Profile,
+
+    /// The projected King level-ups (see `project_king_levels`)
+    king_levels: OnceCell<Vec<KingLevelUp>>,
+}
+
+impl Planner {
+    /// Creates a new planner, computing, sorting and summing all cards once
+    pub fn new(cards: Vec<CardEntry>, profile: Profile) -> Result<Self> {
+        let mut planner = Self {
+            cards,
+            profile,
+            king_levels: OnceCell::new(),
+        };
+        planner.recompute_all()?;
+
+        Ok(planner)
+    }
+
+    /// Returns the sorted cards (including their computed values)
+    pub fn cards(&self) -> &Vec<CardEntry> {
+        &self.cards
+    }
+
+    /// Returns the card with a given UUID
+    pub fn get(&self, uuid: &Uuid) -> Result<&CardEntry> {
+        Ok(&self.cards[self.index_of(uuid)?])
+    }
+
+    /// Returns the profile used for the computations
+    pub fn profile(&self) -> &Profile {
+        &self.profile
+    }
+
+    /// Returns the projected King level-ups
+    pub fn king_levels(&self) -> Result<&[KingLevelUp]> {
+        if let Some(king_levels) = self.king_levels.get() {
+            return Ok(king_levels);
+        }
+
+        let king_levels = project_king_levels(&self.cards, &self.profile.king)?;
+
+        Ok(self.king_levels.get_or_init(|| king_levels))
+    }
+
+    /// Returns whether the King level allows upgrading a card, or when it will (if it's planned)
+    pub fn level_cap(&self, card: &CardEntry) -> Result<Option<LevelCap>> {
+        let data = match &card.computed {
+            Some(data) => data,
+            None => return Ok(None),
+        };
+
+        Ok(Some(match data.level_cap {
+            LevelCap::Allowed => LevelCap::Allowed,
+            _ => lift_level_cap(card.level + 1, self.king_levels()?),
+        }))
+    }
+
+    /// Changes the profile, which affects all cards (keeping the current one if it fails)
+    pub fn set_profile(&mut self, profile: Profile) -> Result<()> {
+        let mut planner = Self {
+            cards: self.cards.clone(),
+            profile,
+            king_levels: OnceCell::new(),
+        };
+        planner.recompute_all()?;
+
+        *self = planner;
+        Ok(())
+    }
+
+    /// Adds a new card at its sorted position
+    pub fn insert(&mut self, mut card: CardEntry) -> Result<()> {
+        // Generate the computed values of the card
+        card.computed = card.calc_remaining(&self.profile)?;
+
+        let index = self.position_of(&card);
+        self.cards.insert(index, card);
+
+        // Only the cards after the new one need new in-order values
+        self.sum_from(index)
+    }
+
+    /// Replaces the card with the same UUID, moving it to its new sorted position
+    pub fn update(&mut self, mut card: CardEntry) -> Result<()> {
+        let index = self.index_of(&card.uuid)?;
+
+        // Generate the computed values of the card
+        card.computed = card.calc_remaining(&self.profile)?;
+
+        // Remove the outdated card entry and insert the new one
+        self.cards.remove(index);
+        let new_index = self.position_of(&card);
+        self.cards.insert(new_index, card);
+
+        // Only the cards after the first changed position need new in-order values
+        self.sum_from(cmp::min(index, new_index))
+    }
+
+    /// Removes the card with a given UUID
+    pub fn remove(&mut self, uuid: &Uuid) -> Result<CardEntry> {
+        let index = self.index_of(uuid)?;
+
+        let card = self.cards.remove(index);
+
+        // Only the cards after the removed one need new in-order values
+        self.sum_from(index)?;
+
+        Ok(card)
+    }
+
+    /// Computes, sorts and sums all cards
+    fn recompute_all(&mut self) -> Result<()> {
+        // Compute the calculated values of all cards
+        CardEntry::compute_all(&mut self.cards, &self.profile)?;
+
+        // Sort by remaining time
+        self.cards
+            .sort_by(CardEntry::sort_by_remaining(&self.profile));
+
+        // Compute the in_order values
+        self.sum_from(0)
+    }
+
+    /// Continues the in-order values from a given index
+    fn sum_from(&mut self, index: usize) -> Result<()> {
+        // The King level projection depends on the whole upgrade plan
+        self.king_levels.take();
+
+        CardEntry::sum_from(&mut self.cards, index)
+    }
+
+    /// Finds the sorted position of a card, placing it after equally ranked ones
+    fn position_of(&self, card: &CardEntry) -> usize {
+        let mut compare = CardEntry::sort_by_remaining(&self.profile);
+
+        self.cards
+            .partition_point(|other| compare(other, card) != cmp::Ordering::Greater)
+    }
+
+    /// Finds the current position of a card
+    fn index_of(&self, uuid: &Uuid) -> Result<usize> {
+        self.cards
+            .iter()
+            .position(|card| card.uuid == *uuid)
+            .ok_or(Error::UnknownCard(*uuid))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::{
+        experience::KingProgress,
+        types::{Arena, Rarity},
+    };
+
+    fn card(name: &str, rarity: Rarity, level: usize, have: usize) -> CardEntry {
+        CardEntry {
+            name: name.to_owned(),
+            rarity,
+            level,
+            have,
+            ..CardEntry::new()
+        }
+    }
+
+    fn planner() -> Planner {
+        let cards = vec![
+            card("Knight", Rarity::Common, 9, 120),
+            card("Archers", Rarity::Common, 11, 30),
+            card("Musketeer", Rarity::Rare, 8, 40),
+            card("Fireball", Rarity::Rare, 10, 300),
+            card("Golem", Rarity::Epic, 8, 5),
+            card("Miner", Rarity::Legendary, 10, 1),
+        ];
+
+        Planner::new(cards, Profile::new(Arena::RoyalArena)).unwrap()
+    }
+
+    /// Checks that the planner has the order and in-order values of a full re-computation
+    fn assert_matches_full(planner: &Planner) {
+        let full = Planner::new(planner.cards().clone(), planner.profile().clone()).unwrap();
+
+        let summary = |planner: &Planner| {
+            planner
+                .cards()
+                .iter()
+                .map(|card| {
+                    let data = card.computed.as_ref();
+                    (
+                        card.name.clone(),
+                        data.map(|data| data.days_remaining),
+                        data.and_then(|data| data.days_in_order),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(summary(planner), summary(&full));
+    }
+
+    #[test]
+    fn inserts_like_a_full_recompute() {
+        let mut planner = planner();
+
+        planner
+            .insert(card("Hog Rider", Rarity::Rare, 9, 60))
+            .unwrap();
+        planner.insert(card("Bats", Rarity::Common, 7, 0)).unwrap();
+
+        assert_matches_full(&planner);
+    }
+
+    #[test]
+    fn removes_like_a_full_recompute() {
+        let mut planner = planner();
+
+        let first = planner.cards()[0].uuid;
+        let middle = planner.cards()[3].uuid;
+        planner.remove(&first).unwrap();
+        planner.remove(&middle).unwrap();
+
+        assert_matches_full(&planner);
+    }
+
+    #[test]
+    fn updates_like_a_full_recompute() {
+        let mut planner = planner();
+
+        // Level up the first card, moving it back
+        let mut leveled = planner.cards()[0].clone();
+        leveled.level += 1;
+        leveled.have = 0;
+        planner.update(leveled).unwrap();
+
+        assert_matches_full(&planner);
+
+        // Give the last card (which may never be done) enough cards to move it forward
+        let mut collected = planner.cards().last().unwrap().clone();
+        collected.have += 500;
+        planner.update(collected).unwrap();
+
+        assert_matches_full(&planner);
+    }
+
+    #[test]
+    fn lifts_level_caps_with_the_projected_king_levels() {
+        let mut profile = Profile::new(Arena::RoyalArena);
+        profile.king = KingProgress {
+            level: 9,
+            experience: 0,
+            donation_xp_per_week: 7000,
+        };
+
+        let cards = vec![
+            card("Knight", Rarity::Common, 8, 0),
+            card("Archers", Rarity::Common, 9, 0),
+        ];
+        let planner = Planner::new(cards.clone(), profile.clone()).unwrap();
+        let level_cap = |name: &str| {
+            let card = planner
+                .cards()
+                .iter()
+                .find(|card| card.name == name)
+                .unwrap();
+            planner.level_cap(card).unwrap()
+        };
+
+        assert_eq!(level_cap("Knight"), Some(LevelCap::Allowed));
+        assert!(matches!(level_cap("Archers"), Some(LevelCap::LiftedOn(_))));
+
+        // Without donations, only upgrades within the cap could level up the King
+        profile.king.donation_xp_per_week = 0;
+        let planner = Planner::new(vec![cards[1].clone()], profile).unwrap();
+
+        assert_eq!(
+            planner.level_cap(&planner.cards()[0]).unwrap(),
+            Some(LevelCap::Blocked)
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use super::{
+    experience::KingProgress,
+    income::IncomeModel,
+    transactions::FillRate,
+    types::{Arena, RequestFrequency},
+};
+use chrono::{Local, NaiveDate};
+use serde_derive::{Deserialize, Serialize};
+
+/// The player-specific settings the calculations depend on
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct Profile {
+    /// The arena the player is in
+    pub arena: Arena,
+
+    /// The trophies of the player (if known), from which the arena is inferred
+    #[serde(default)]
+    pub trophies: Option<usize>,
+
+    /// The amount of requests placed per week
+    #[serde(default)]
+    pub schedule: RequestFrequency,
+
+    /// The sources of cards besides requests
+    #[serde(default)]
+    pub income: IncomeModel,
+
+    /// The arenas the player plans to reach (in any order)
+    #[serde(default)]
+    pub progression: Vec<ArenaMilestone>,
+
+    /// The King level and experience of the player
+    #[serde(default)]
+    pub king: KingProgress,
+
+    /// The share of each request which gets filled (calibrated from the logged requests)
+    #[serde(default)]
+    pub fill_rate: FillRate,
+}
+
+/// A planned advancement to another arena
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct ArenaMilestone {
+    /// The arena to be reached
+    pub arena: Arena,
+
+    /// The day on which the arena will be reached
+    pub date: NaiveDate,
+}
+
+impl Profile {
+    pub fn new(arena: Arena) -> Self {
+        Self {
+            arena,
+            trophies: None,
+            schedule: RequestFrequency::default(),
+            income: IncomeModel::default(),
+            progression: vec![],
+            king: KingProgress::default(),
+            fill_rate: FillRate::default(),
+        }
+    }
+
+    /// Creates a profile in the arena matching the amount of trophies
+    pub fn from_trophies(trophies: usize) -> Self {
+        let mut profile = Self::default();
+        profile.set_trophies(trophies);
+        profile
+    }
+
+    /// Sets the trophies of the player, moving them to the matching arena
+    pub fn set_trophies(&mut self, trophies: usize) {
+        self.arena = Arena::from_trophies(trophies);
+        self.trophies = Some(trophies);
+        self.forget_reached_milestones();
+    }
+
+    /// Sets the arena of the player, forgetting their trophies
+    pub fn set_arena(&mut self, arena: Arena) {
+        self.arena = arena;
+        self.trophies = None;
+        self.forget_reached_milestones();
+    }
+
+    /// Removes the milestones reached already, which `arena_schedule` would prefer to the arena
+    fn forget_reached_milestones(&mut self) {
+        let today = Local::now().date_naive();
+
+        self.progression.retain(|milestone| milestone.date > today);
+    }
+
+    /**
+    Returns the arenas the player will be in, starting today
+
+    Each entry contains the amount of weeks from today on after which the arena is reached.
+    The first entry always starts at zero weeks, using the latest milestone which was reached
+    already (or the current arena if there is none).
+    */
+    pub fn arena_schedule(&self, today: NaiveDate) -> Vec<(f64, &Arena)> {
+        let mut milestones = self.progression.iter().collect::<Vec<_>>();
+        milestones.sort_by_key(|milestone| milestone.date);
+
+        let mut schedule = vec![(0., &self.arena)];
+
+        for milestone in milestones {
+            let weeks = (milestone.date - today).num_days() as f64 / 7.;
+
+            if weeks <= 0. {
+                // The milestone has been reached already
+                schedule[0].1 = &milestone.arena;
+            } else {
+                schedule.push((weeks, &milestone.arena));
+            }
+        }
+
+        schedule
+    }
+
+    /// Returns the arena the player is in on a given day (see `arena_schedule`)
+    pub fn current_arena(&self, today: NaiveDate) -> &Arena {
+        self.arena_schedule(today)[0].1
+    }
+}
+
+impl Default for Profile {
+    /// Defaults to the LegendaryArena, only counting requests
+    fn default() -> Self {
+        Self::new(Arena::LegendaryArena)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn milestone(arena: Arena, days: i64) -> ArenaMilestone {
+        ArenaMilestone {
+            arena,
+            date: Local::now().date_naive() + Duration::days(days),
+        }
+    }
+
+    #[test]
+    fn schedules_the_arenas_in_order() {
+        let mut profile = Profile::new(Arena::GoblinStadium);
+        profile.progression = vec![
+            milestone(Arena::RoyalArena, 14),
+            milestone(Arena::BonePit, 7),
+        ];
+
+        let today = Local::now().date_naive();
+
+        assert_eq!(
+            profile.arena_schedule(today),
+            vec![
+                (0., &Arena::GoblinStadium),
+                (1., &Arena::BonePit),
+                (2., &Arena::RoyalArena),
+            ]
+        );
+        assert_eq!(profile.current_arena(today), &Arena::GoblinStadium);
+    }
+
+    #[test]
+    fn reached_milestones_replace_the_current_arena() {
+        let mut profile = Profile::new(Arena::GoblinStadium);
+        profile.progression = vec![
+            milestone(Arena::BonePit, -14),
+            milestone(Arena::BarbarianBowl, -7),
+            milestone(Arena::RoyalArena, 7),
+        ];
+
+        let today = Local::now().date_naive();
+
+        assert_eq!(
+            profile.arena_schedule(today),
+            vec![(0., &Arena::BarbarianBowl), (1., &Arena::RoyalArena)]
+        );
+        assert_eq!(profile.current_arena(today), &Arena::BarbarianBowl);
+
+        // Looking ahead, the next milestone is reached too
+        let later = today + Duration::days(7);
+        assert_eq!(profile.current_arena(later), &Arena::RoyalArena);
+    }
+
+    #[test]
+    fn forgets_reached_milestones() {
+        let mut profile = Profile::new(Arena::GoblinStadium);
+        profile.progression = vec![
+            milestone(Arena::BonePit, -7),
+            milestone(Arena::BarbarianBowl, 0),
+            milestone(Arena::RoyalArena, 7),
+        ];
+
+        profile.forget_reached_milestones();
+
+        assert_eq!(profile.progression, vec![milestone(Arena::RoyalArena, 7)]);
+    }
+}
@@ -0,0 +1,277 @@
+use super::{
+    calc::LevelCap,
+    profile::Profile,
+    types::{get_request_size, CardEntry, Rarity},
+};
+use chrono::{DateTime, Datelike, Local, NaiveDate};
+use serde_derive::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// The amount of requests which can be made per day
+pub const DAILY_REQUESTS: usize = 3;
+
+/// Keeps track of the requests a player has already made
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Default)]
+pub struct RequestLog {
+    /// The day of the last request
+    pub day: Option<NaiveDate>,
+
+    /// The amount of requests made on that day
+    pub made: usize,
+
+    /// The day of the last epic request
+    pub epic_on: Option<NaiveDate>,
+}
+
+impl RequestLog {
+    /// Returns the amount of requests made on a given day
+    pub fn made_on(&self, today: NaiveDate) -> usize {
+        if self.day == Some(today) {
+            self.made
+        } else {
+            0
+        }
+    }
+
+    /// Checks if an epic card was requested in the week of a given day
+    pub fn epic_made_in_week_of(&self, today: NaiveDate) -> bool {
+        self.epic_on
+            .is_some_and(|day| day.iso_week() == today.iso_week())
+    }
+
+    /// Records a request for a card of a given rarity
+    pub fn record(&mut self, rarity: &Rarity, today: NaiveDate) {
+        self.made = self.made_on(today) + 1;
+        self.day = Some(today);
+
+        if *rarity == Rarity::Epic {
+            self.epic_on = Some(today);
+        }
+    }
+}
+
+/// Why a card should be requested
+#[derive(Debug, PartialEq, Clone)]
+pub enum Reason {
+    /// The card is the epic one to be upgraded first, and this week's epic request is still open
+    WeeklyEpic,
+
+    /// The card is the next one to be upgraded in the planned order
+    NextInOrder,
+
+    /// The previous card needs fewer requests than there are left today
+    AfterUpgrade { previous: String },
+}
+
+/// A card to request today
+#[derive(Debug, PartialEq, Clone)]
+pub struct Recommendation {
+    pub uuid: Uuid,
+    pub name: String,
+    pub rarity: Rarity,
+
+    /// The amount of today's requests to use for the card
+    pub requests: usize,
+
+    /// The amount of cards missing for the next upgrade
+    pub cards_remaining: usize,
+
+    /// The day the upgrade is planned for
+    pub done_in_order_on: Option<DateTime<Local>>,
+
+    pub reason: Reason,
+}
+
+/**
+Recommends the cards to request today
+
+Follows the order of the cards (as sorted by the `Planner`), skipping cards
+which have enough cards, can't be upgraded at the current King level,
+can't be requested in the current arena or don't get requested according to the schedule.
+Epic cards take up one request per week, the rest go to common and rare cards.
+*/
+pub fn recommend(
+    cards: &[CardEntry],
+    profile: &Profile,
+    log: &RequestLog,
+    today: NaiveDate,
+) -> Vec<Recommendation> {
+    let request_size = get_request_size(profile.current_arena(today));
+    let mut open = DAILY_REQUESTS.saturating_sub(log.made_on(today));
+    let mut recommendations = Vec::new();
+
+    // The cards still needing cards, in the planned order
+    let candidates = cards.iter().filter(|card| {
+        card.computed
+            .as_ref()
+            .is_some_and(|data| data.cards_remaining > 0 && data.level_cap == LevelCap::Allowed)
+            && request_size.get(&card.rarity) > 0
+            && profile.schedule.get(&card.rarity) > 0.
+    });
+
+    // Use the epic request of the week first
+    if open > 0 && !log.epic_made_in_week_of(today) {
+        if let Some(card) = candidates.clone().find(|card| card.rarity == Rarity::Epic) {
+            recommendations.push(Recommendation::new(card, 1, Reason::WeeklyEpic));
+            open -= 1;
+        }
+    }
+
+    // Fill the other requests with common and rare cards
+    let mut previous: Option<&CardEntry> = None;
+
+    for card in candidates.filter(|card| matches!(card.rarity, Rarity::Common | Rarity::Rare)) {
+        if open == 0 {
+            break;
+        }
+
+        let cards_remaining = card.computed.as_ref().unwrap().cards_remaining;
+        let size = request_size.get(&card.rarity);
+
+        // Don't request more than needed for the upgrade
+        let requests = cards_remaining.div_ceil(size).min(open);
+
+        let reason = match previous {
+            Some(previous) => Reason::AfterUpgrade {
+                previous: previous.name.clone(),
+            },
+            None => Reason::NextInOrder,
+        };
+
+        recommendations.push(Recommendation::new(card, requests, reason));
+        open -= requests;
+        previous = Some(card);
+    }
+
+    recommendations
+}
+
+impl Recommendation {
+    fn new(card: &CardEntry, requests: usize, reason: Reason) -> Self {
+        let data = card.computed.as_ref();
+
+        Self {
+            uuid: card.uuid,
+            name: card.name.clone(),
+            rarity: card.rarity.clone(),
+            requests,
+            cards_remaining: data.map_or(0, |data| data.cards_remaining),
+            done_in_order_on: data.and_then(|data| data.done_in_order_on),
+            reason,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::{profile::ArenaMilestone, types::Arena};
+    use chrono::{Duration, Local};
+
+    fn card(name: &str, rarity: Rarity, level: usize, profile: &Profile) -> CardEntry {
+        let mut card = CardEntry {
+            name: name.to_owned(),
+            rarity,
+            level,
+            ..CardEntry::new()
+        };
+        card.computed = card.calc_remaining(profile).unwrap();
+
+        card
+    }
+
+    fn today() -> NaiveDate {
+        Local::now().date_naive()
+    }
+
+    #[test]
+    fn requests_an_epic_card_once_per_week() {
+        let profile = Profile::new(Arena::LegendaryArena);
+        let cards = vec![
+            card("Knight", Rarity::Common, 9, &profile),
+            card("Prince", Rarity::Epic, 9, &profile),
+        ];
+
+        let recommendations = recommend(&cards, &profile, &RequestLog::default(), today());
+        assert_eq!(recommendations[0].name, "Prince");
+        assert_eq!(recommendations[0].requests, 1);
+        assert_eq!(recommendations[0].reason, Reason::WeeklyEpic);
+        assert_eq!(recommendations[1].name, "Knight");
+        assert_eq!(recommendations[1].reason, Reason::NextInOrder);
+
+        let mut log = RequestLog::default();
+        log.record(&Rarity::Epic, today());
+
+        let recommendations = recommend(&cards, &profile, &log, today());
+        assert!(recommendations.iter().all(|r| r.rarity != Rarity::Epic));
+    }
+
+    #[test]
+    fn stays_within_the_daily_requests() {
+        let profile = Profile::new(Arena::LegendaryArena);
+        let cards = vec![
+            card("Knight", Rarity::Common, 9, &profile),
+            card("Archers", Rarity::Common, 9, &profile),
+            card("Goblins", Rarity::Common, 9, &profile),
+            card("Bats", Rarity::Common, 9, &profile),
+        ];
+        let total = |log: &RequestLog| {
+            recommend(&cards, &profile, log, today())
+                .iter()
+                .map(|recommendation| recommendation.requests)
+                .sum::<usize>()
+        };
+
+        assert_eq!(total(&RequestLog::default()), DAILY_REQUESTS);
+
+        let mut log = RequestLog::default();
+        log.record(&Rarity::Common, today());
+        log.record(&Rarity::Common, today());
+        assert_eq!(total(&log), DAILY_REQUESTS - 2);
+
+        log.record(&Rarity::Common, today());
+        assert_eq!(total(&log), 0);
+    }
+
+    #[test]
+    fn skips_capped_cards_and_rarities_without_requests() {
+        let mut profile = Profile::new(Arena::LegendaryArena);
+        profile.king.level = 9;
+
+        // Level 10 needs a higher King level
+        let capped = vec![card("Knight", Rarity::Common, 9, &profile)];
+        assert!(recommend(&capped, &profile, &RequestLog::default(), today()).is_empty());
+
+        // The Training Camp has no requests, even if a later arena does
+        let mut profile = Profile::new(Arena::TrainingCamp);
+        profile.progression.push(ArenaMilestone {
+            arena: Arena::LegendaryArena,
+            date: today() + Duration::days(14),
+        });
+
+        let unrequestable = vec![card("Knight", Rarity::Common, 9, &profile)];
+        assert!(unrequestable[0].computed.is_some());
+        assert!(recommend(&unrequestable, &profile, &RequestLog::default(), today()).is_empty());
+    }
+
+    #[test]
+    fn records_requests_per_day_and_epic_requests_per_iso_week() {
+        // A Monday, the first day of an ISO week
+        let monday = NaiveDate::from_ymd_opt(2024, 1, 8).unwrap();
+        let sunday = monday + Duration::days(6);
+
+        let mut log = RequestLog::default();
+        log.record(&Rarity::Epic, monday);
+        log.record(&Rarity::Common, monday);
+        assert_eq!(log.made_on(monday), 2);
+
+        // A new day starts counting again, but the epic request lasts all week
+        log.record(&Rarity::Common, sunday);
+        assert_eq!(log.made_on(sunday), 1);
+        assert_eq!(log.made_on(monday), 0);
+        assert!(log.epic_made_in_week_of(sunday));
+
+        // The next ISO week starts on the following Monday
+        assert!(!log.epic_made_in_week_of(sunday + Duration::days(1)));
+    }
+}
@@ -0,0 +1,172 @@
+use super::{error::Result, profile::Profile, types::CardEntry};
+use strum_macros::{EnumIter, EnumString};
+use uuid::Uuid;
+
+/// The order in which cards get requested (and upgraded)
+#[derive(Debug, EnumIter, EnumString, PartialEq, Clone)]
+pub enum UpgradeOrder {
+    /// Cards closest to their next level first
+    ByRemaining,
+
+    /// Cards needing the least gold first
+    ByGold,
+
+    /// Cards in the order of the collection
+    AsListed,
+}
+
+/**
+A named "what-if" variant of the player's situation
+
+Running a scenario computes a copy of the collection, so neither the
+collection nor its event log are changed.
+*/
+#[derive(Debug, PartialEq, Clone)]
+pub struct Scenario {
+    pub name: String,
+    pub profile: Profile,
+    pub order: UpgradeOrder,
+}
+
+/// The computed cards of a scenario
+pub struct ScenarioResult {
+    pub name: String,
+
+    /// The cards in the order of the scenario
+    /// (cards which don't get any cards in the scenario have no computed values)
+    pub cards: Vec<CardEntry>,
+}
+
+impl Scenario {
+    pub fn new(name: &str, profile: Profile) -> Self {
+        Self {
+            name: name.to_owned(),
+            profile,
+            order: UpgradeOrder::ByRemaining,
+        }
+    }
+
+    /// Computes, orders and sums a copy of the cards according to the scenario
+    pub fn run(&self, cards: &[CardEntry]) -> Result<ScenarioResult> {
+        let mut cards = cards.to_vec();
+
+        // Compute the calculated values of all cards (keeping the ones which never get done)
+        CardEntry::compute_all(&mut cards, &self.profile)?;
+
+        match self.order {
+            UpgradeOrder::ByRemaining => {
+                cards.sort_by(CardEntry::sort_by_remaining(&self.profile));
+            }
+            UpgradeOrder::ByGold => {
+                cards.sort_by_key(|card| card.get_needed_gold().unwrap_or_default());
+            }
+            UpgradeOrder::AsListed => (),
+        }
+
+        // Compute the in_order values
+        CardEntry::sum_all(&mut cards)?;
+
+        Ok(ScenarioResult {
+            name: self.name.clone(),
+            cards,
+        })
+    }
+}
+
+impl ScenarioResult {
+    /// Finds the computed card with a given UUID
+    pub fn find(&self, uuid: &Uuid) -> Option<&CardEntry> {
+        self.cards.iter().find(|card| &card.uuid == uuid)
+    }
+
+    /// Returns how many days later (or earlier, if negative) a card is done in order
+    /// compared to another computation of it
+    pub fn days_in_order_delta(&self, baseline: &CardEntry) -> Option<f64> {
+        let days = self
+            .find(&baseline.uuid)?
+            .computed
+            .as_ref()?
+            .days_in_order?;
+        let baseline_days = baseline.computed.as_ref()?.days_in_order?;
+
+        Some(days - baseline_days)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::types::{Arena, Rarity};
+
+    fn card(name: &str, rarity: Rarity, level: usize) -> CardEntry {
+        CardEntry {
+            name: name.to_owned(),
+            rarity,
+            level,
+            ..CardEntry::new()
+        }
+    }
+
+    fn names(result: &ScenarioResult) -> Vec<&str> {
+        result.cards.iter().map(|card| card.name.as_str()).collect()
+    }
+
+    fn cards() -> Vec<CardEntry> {
+        vec![
+            card("Knight", Rarity::Common, 11),
+            card("Archers", Rarity::Common, 5),
+            card("Prince", Rarity::Epic, 8),
+        ]
+    }
+
+    #[test]
+    fn orders_the_cards() {
+        let mut scenario = Scenario::new("Gold", Profile::new(Arena::LegendaryArena));
+
+        scenario.order = UpgradeOrder::ByGold;
+        let result = scenario.run(&cards()).unwrap();
+        assert_eq!(names(&result), vec!["Archers", "Prince", "Knight"]);
+
+        scenario.order = UpgradeOrder::AsListed;
+        let result = scenario.run(&cards()).unwrap();
+        assert_eq!(names(&result), vec!["Knight", "Archers", "Prince"]);
+    }
+
+    #[test]
+    fn keeps_cards_which_never_get_done() {
+        let mut profile = Profile::new(Arena::LegendaryArena);
+        profile.schedule.epic = 0.;
+
+        let result = Scenario::new("No epics", profile).run(&cards()).unwrap();
+        let prince = result
+            .cards
+            .iter()
+            .find(|card| card.name == "Prince")
+            .unwrap();
+
+        assert_eq!(result.cards.len(), 3);
+        assert!(prince.computed.is_none());
+    }
+
+    #[test]
+    fn compares_the_days_in_order() {
+        let cards = cards();
+        let baseline = Scenario::new("Baseline", Profile::new(Arena::LegendaryArena))
+            .run(&cards)
+            .unwrap();
+
+        // Half the requests take longer
+        let mut profile = Profile::new(Arena::LegendaryArena);
+        profile.schedule.common /= 2.;
+        let slower = Scenario::new("Slower", profile).run(&cards).unwrap();
+
+        let archers = baseline.find(&cards[1].uuid).unwrap();
+        let prince = baseline.find(&cards[2].uuid).unwrap();
+
+        assert_eq!(baseline.days_in_order_delta(archers), Some(0.));
+        assert!(slower.days_in_order_delta(archers).unwrap() > 0.);
+
+        // The epic requests stay the same
+        assert_eq!(slower.days_in_order_delta(prince), Some(0.));
+    }
+}
@@ -0,0 +1,307 @@
+use super::types::{CardEntry, CardType, Rarity, MAX_LEVEL};
+use serde_derive::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use strum_macros::{EnumIter, EnumString};
+
+/// The preferences of the user, which don't affect any calculations
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Default)]
+pub struct Settings {
+    /// How the cards are listed
+    #[serde(default)]
+    pub view: ListView,
+
+    /// Where to sync the event logs to (if anywhere)
+    #[serde(default)]
+    pub sync: SyncSettings,
+
+    /// The language and the number and date formats
+    #[serde(default)]
+    pub locale: Locale,
+
+    /// The colors of the user interface
+    #[serde(default)]
+    pub theme: Theme,
+
+    /// Whether the cards are shown as a table or as a grid of tiles
+    #[serde(default)]
+    pub layout: Layout,
+}
+
+/// The languages of the user interface (including their number and date formats)
+#[derive(
+    Serialize, Deserialize, Debug, EnumIter, EnumString, PartialEq, Eq, Clone, Copy, Default,
+)]
+pub enum Locale {
+    #[default]
+    English,
+    German,
+}
+
+/// The ways of showing the listed cards
+#[derive(Serialize, Deserialize, Debug, EnumIter, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Layout {
+    /// A row with all values per card, which can be edited
+    #[default]
+    Table,
+
+    /// A tile with the level and progress per card, like the collection of the game
+    Collection,
+}
+
+/// The color schemes of the stylesheet (see `src/css/main.css`)
+#[derive(
+    Serialize, Deserialize, Debug, EnumIter, EnumString, PartialEq, Eq, Clone, Copy, Default,
+)]
+pub enum Theme {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl Theme {
+    /// Returns the value of the `data-theme` attribute selecting the theme
+    pub fn attribute(self) -> &'static str {
+        match self {
+            Theme::Dark => "dark",
+            Theme::Light => "light",
+        }
+    }
+}
+
+/// The sync server and the profile to sync with (see `logic::sync`)
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Default)]
+pub struct SyncSettings {
+    /// The URL of the server, like `http://localhost:8787`
+    pub server: String,
+
+    /// The name of the profile on the server
+    pub profile: String,
+}
+
+/// The columns the cards can be sorted by
+#[derive(Serialize, Deserialize, Debug, EnumIter, EnumString, PartialEq, Clone)]
+pub enum SortColumn {
+    /// The order in which the cards get upgraded (see `CardEntry::sort_by_remaining`)
+    Planned,
+    Name,
+    Level,
+    Rarity,
+    Days,
+    Gold,
+
+    /// The share of the cards needed for the next level which are available
+    Progress,
+}
+
+/// Which cards are listed in which order
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct ListView {
+    pub sort: SortColumn,
+    pub descending: bool,
+
+    /// Only list cards containing this text in their name (ignoring the case)
+    pub search: String,
+
+    /// Only list cards of this rarity (if any)
+    pub rarity: Option<Rarity>,
+
+    /// Only list cards of this type (if any)
+    pub card_type: Option<CardType>,
+
+    /// Hide cards on the highest level
+    pub hide_maxed: bool,
+
+    /// Only list the cards of the player's deck
+    #[serde(default)]
+    pub deck_only: bool,
+}
+
+impl Default for ListView {
+    /// Defaults to listing all cards in the planned order
+    fn default() -> Self {
+        Self {
+            sort: SortColumn::Planned,
+            descending: false,
+            search: String::new(),
+            rarity: None,
+            card_type: None,
+            hide_maxed: false,
+            deck_only: false,
+        }
+    }
+}
+
+impl ListView {
+    /// Sorts by a column, reversing the order if it's sorted by it already
+    pub fn toggle_sort(&mut self, column: SortColumn) {
+        if self.sort == column {
+            self.descending = !self.descending;
+        } else {
+            self.sort = column;
+            self.descending = false;
+        }
+    }
+
+    /// Returns the cards to list in their order (without changing the given list)
+    pub fn apply<'a>(&self, cards: &'a [CardEntry]) -> Vec<&'a CardEntry> {
+        let search = self.search.to_lowercase();
+
+        let mut listed = cards
+            .iter()
+            .filter(|card| card.name.to_lowercase().contains(&search))
+            .filter(|card| self.rarity.as_ref().is_none_or(|r| *r == card.rarity))
+            .filter(|card| self.card_type.as_ref().is_none_or(|t| *t == card.card_type))
+            .filter(|card| !(self.hide_maxed && card.level >= MAX_LEVEL))
+            .filter(|card| !self.deck_only || card.in_deck)
+            .collect::<Vec<_>>();
+
+        // Sorts stably, keeping the planned order for equal values
+        listed.sort_by(|a, b| {
+            let ordering = self.compare(a, b);
+
+            if self.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+
+        listed
+    }
+
+    fn compare(&self, a: &CardEntry, b: &CardEntry) -> Ordering {
+        // Cards without a value go last
+        let days = |card: &CardEntry| {
+            card.computed
+                .as_ref()
+                .and_then(|data| data.days_in_order)
+                .unwrap_or(f64::INFINITY)
+        };
+
+        let progress = |card: &CardEntry| card.get_upgrade_progress().unwrap_or_default();
+
+        match self.sort {
+            SortColumn::Planned => Ordering::Equal,
+            SortColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            SortColumn::Level => a.level.cmp(&b.level),
+            SortColumn::Rarity => a.rarity.cmp(&b.rarity),
+            SortColumn::Days => days(a).total_cmp(&days(b)),
+            SortColumn::Gold => a
+                .get_needed_gold()
+                .unwrap_or_default()
+                .cmp(&b.get_needed_gold().unwrap_or_default()),
+            SortColumn::Progress => progress(a).total_cmp(&progress(b)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logic::profile::Profile;
+
+    fn card(name: &str, rarity: Rarity, card_type: CardType, level: usize) -> CardEntry {
+        let mut card = CardEntry {
+            name: name.to_owned(),
+            rarity,
+            card_type,
+            level,
+            ..CardEntry::new()
+        };
+        card.computed = card.calc_remaining(&Profile::default()).unwrap();
+
+        card
+    }
+
+    /// The cards in their planned order
+    fn cards() -> Vec<CardEntry> {
+        let mut prince = card("Prince", Rarity::Epic, CardType::Troop, 9);
+        prince.in_deck = true;
+
+        let mut cards = vec![
+            card("Knight", Rarity::Common, CardType::Troop, 9),
+            card("Arrows", Rarity::Common, CardType::Spell, MAX_LEVEL),
+            prince,
+            card("Cannon", Rarity::Common, CardType::Building, 9),
+        ];
+        CardEntry::sum_all(&mut cards).unwrap();
+
+        cards
+    }
+
+    fn names(listed: &[&CardEntry]) -> Vec<String> {
+        listed.iter().map(|card| card.name.clone()).collect()
+    }
+
+    #[test]
+    fn filters_the_cards() {
+        let cards = cards();
+        let listed = |view: ListView| names(&view.apply(&cards));
+
+        assert_eq!(
+            listed(ListView {
+                search: "kNI".to_owned(),
+                ..ListView::default()
+            }),
+            vec!["Knight"]
+        );
+        assert_eq!(
+            listed(ListView {
+                rarity: Some(Rarity::Epic),
+                ..ListView::default()
+            }),
+            vec!["Prince"]
+        );
+        assert_eq!(
+            listed(ListView {
+                card_type: Some(CardType::Spell),
+                ..ListView::default()
+            }),
+            vec!["Arrows"]
+        );
+        assert_eq!(
+            listed(ListView {
+                hide_maxed: true,
+                ..ListView::default()
+            }),
+            vec!["Knight", "Prince", "Cannon"]
+        );
+        assert_eq!(
+            listed(ListView {
+                deck_only: true,
+                ..ListView::default()
+            }),
+            vec!["Prince"]
+        );
+    }
+
+    #[test]
+    fn sorts_stably() {
+        let mut cards = cards();
+        let mut view = ListView::default();
+        assert_eq!(
+            names(&view.apply(&cards)),
+            vec!["Knight", "Arrows", "Prince", "Cannon"]
+        );
+
+        // Equal levels keep the planned order, in both directions
+        view.toggle_sort(SortColumn::Level);
+        assert_eq!(
+            names(&view.apply(&cards)),
+            vec!["Knight", "Prince", "Cannon", "Arrows"]
+        );
+
+        view.toggle_sort(SortColumn::Level);
+        assert!(view.descending);
+        assert_eq!(
+            names(&view.apply(&cards)),
+            vec!["Arrows", "Knight", "Prince", "Cannon"]
+        );
+
+        // Cards without a planned day go last
+        cards[0].computed = None;
+        view.toggle_sort(SortColumn::Days);
+        assert!(!view.descending);
+        assert_eq!(names(&view.apply(&cards)).last().unwrap(), "Knight");
+    }
+}
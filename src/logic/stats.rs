@@ -0,0 +1,211 @@
+use super::{
+    error::Result,
+    types::{CardEntry, Rarity, MAX_LEVEL},
+};
+use chrono::{DateTime, Duration, Local, Utc};
+use libocc::Projector;
+use strum::IntoEnumIterator;
+
+/// Aggregated values of a whole collection of cards
+#[derive(Debug, PartialEq, Clone)]
+pub struct CollectionStats {
+    /// The amount of cards in the collection
+    pub cards: usize,
+
+    /// The amount of cards on each level (by index + 1), by rarity
+    pub distribution: Vec<(Rarity, [usize; MAX_LEVEL])>,
+
+    /// The average level of all cards (if there are any)
+    pub average_level: Option<f64>,
+
+    /// The amount of cards missing to upgrade every card to the highest level
+    pub cards_to_max: usize,
+
+    /// The amount of gold needed to upgrade every card to the highest level
+    pub gold_to_max: usize,
+
+    /// The share of all cards needed for a maxed collection which were collected already
+    pub completion: f64,
+}
+
+impl CollectionStats {
+    pub fn from_cards(cards: &[CardEntry]) -> Result<Self> {
+        let mut distribution = Rarity::iter()
+            .map(|rarity| (rarity, [0; MAX_LEVEL]))
+            .collect::<Vec<_>>();
+
+        let mut level_sum = 0;
+        let mut cards_to_max = 0;
+        let mut gold_to_max = 0;
+        let mut cards_for_max = 0;
+
+        for card in cards {
+            card.check_level()?;
+
+            // Count the card on its level
+            if let Some((_, levels)) = distribution.iter_mut().find(|(r, _)| *r == card.rarity) {
+                levels[card.level - 1] += 1;
+            }
+
+            level_sum += card.level;
+
+            // Add up the upgrades from the lowest level on, the ones to come in particular
+            let mut upgrade = card.clone();
+            let mut cards_to_come = 0;
+
+            for level in card.rarity.min_level()..MAX_LEVEL {
+                upgrade.level = level;
+
+                cards_for_max += upgrade.get_needed_cards()?;
+
+                if level >= card.level {
+                    cards_to_come += upgrade.get_needed_cards()?;
+                    gold_to_max += upgrade.get_needed_gold()?;
+                }
+            }
+
+            cards_to_max += cards_to_come.saturating_sub(card.have);
+        }
+
+        Ok(Self {
+            cards: cards.len(),
+            distribution,
+            average_level: if cards.is_empty() {
+                None
+            } else {
+                Some(level_sum as f64 / cards.len() as f64)
+            },
+            cards_to_max,
+            gold_to_max,
+            completion: if cards_for_max == 0 {
+                1.
+            } else {
+                1. - cards_to_max as f64 / cards_for_max as f64
+            },
+        })
+    }
+}
+
+/// The state of the collection at some point in the past
+#[derive(Debug, PartialEq, Clone)]
+pub struct Trend {
+    /// The moment the state is from
+    pub date: DateTime<Local>,
+
+    pub stats: CollectionStats,
+}
+
+/**
+Replays the event log to get the state of the collection once per week
+
+Covers up to the given amount of weeks before now (oldest first),
+leaving out the weeks before the event log was started.
+*/
+pub fn trends(log: &Projector<CardEntry>, weeks: usize) -> Result<Vec<Trend>> {
+    let now = Utc::now();
+    let mut trends = Vec::new();
+
+    for weeks_ago in (0..=weeks).rev() {
+        let date = now - Duration::weeks(weeks_ago as i64);
+
+        if let Some(cards) = log.project_at(&date) {
+            trends.push(Trend {
+                date: date.with_timezone(&Local),
+                stats: CollectionStats::from_cards(&cards)?,
+            });
+        }
+    }
+
+    Ok(trends)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use libocc::Event;
+
+    fn card(rarity: Rarity, level: usize, have: usize) -> CardEntry {
+        CardEntry {
+            rarity,
+            level,
+            have,
+            ..CardEntry::new()
+        }
+    }
+
+    /// A common card with 1000 of the 5000 cards for level 13, a maxed epic one
+    /// and a legendary one with more cards than needed for level 13
+    fn mixed() -> Vec<CardEntry> {
+        vec![
+            card(Rarity::Common, 12, 1000),
+            card(Rarity::Epic, MAX_LEVEL, 0),
+            card(Rarity::Legendary, 12, 30),
+        ]
+    }
+
+    #[test]
+    fn sums_up_an_empty_collection() {
+        let stats = CollectionStats::from_cards(&[]).unwrap();
+
+        assert_eq!(stats.cards, 0);
+        assert_eq!(stats.distribution.len(), 4);
+        assert!(stats
+            .distribution
+            .iter()
+            .all(|(_, levels)| levels.iter().all(|count| *count == 0)));
+        assert_eq!(stats.average_level, None);
+        assert_eq!(stats.cards_to_max, 0);
+        assert_eq!(stats.gold_to_max, 0);
+        assert_eq!(stats.completion, 1.);
+    }
+
+    #[test]
+    fn sums_up_a_mixed_collection() {
+        let cards = mixed();
+        let stats = CollectionStats::from_cards(&cards).unwrap();
+
+        let levels = |rarity: Rarity| {
+            stats
+                .distribution
+                .iter()
+                .find(|(r, _)| *r == rarity)
+                .map(|(_, levels)| *levels)
+                .unwrap()
+        };
+
+        assert_eq!(stats.cards, 3);
+        assert_eq!(levels(Rarity::Common)[11], 1);
+        assert_eq!(levels(Rarity::Rare), [0; MAX_LEVEL]);
+        assert_eq!(levels(Rarity::Epic)[MAX_LEVEL - 1], 1);
+        assert_eq!(levels(Rarity::Legendary)[11], 1);
+        assert_eq!(stats.average_level, Some(37. / 3.));
+
+        // Only the common card misses cards, the legendary one has more than enough
+        assert_eq!(stats.cards_to_max, 4000);
+        assert_eq!(
+            stats.gold_to_max,
+            cards[0].get_needed_gold().unwrap() + cards[2].get_needed_gold().unwrap()
+        );
+
+        // All cards from their first level on: 9586 common, 386 epic and 36 legendary ones
+        assert_eq!(stats.completion, 1. - 4000. / (9586. + 386. + 36.));
+    }
+
+    #[test]
+    fn leaves_out_the_weeks_before_the_log() {
+        let mut log = Projector::new();
+        assert_eq!(trends(&log, 4).unwrap().len(), 1);
+
+        for card in mixed() {
+            log.push(Event::create(card)).unwrap();
+        }
+
+        let trends = trends(&log, 4).unwrap();
+
+        assert_eq!(trends.len(), 1);
+        assert_eq!(
+            trends[0].stats,
+            CollectionStats::from_cards(&mixed()).unwrap()
+        );
+    }
+}
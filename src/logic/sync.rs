@@ -0,0 +1,82 @@
+use super::{
+    error::{Error, Result},
+    events::{EventSourcingService, MergeReport},
+    transactions::CardTransaction,
+    types::CardEntry,
+};
+use libocc::Projector;
+use serde_derive::{Deserialize, Serialize};
+
+/// The longest name of a profile on a sync server
+pub const MAX_PROFILE_LENGTH: usize = 64;
+
+/**
+The event logs of a profile, as exchanged with a sync server
+
+Pushing them to `events_url` returns the server's logs merged with the pushed ones,
+which can then be merged into the local logs (see `EventSourcingService::merge`).
+*/
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SyncLog {
+    pub cards: Projector<CardEntry>,
+    pub transactions: Projector<CardTransaction>,
+}
+
+impl SyncLog {
+    /// An empty log (of a profile unknown to the server)
+    pub fn new() -> Self {
+        Self {
+            cards: Projector::new(),
+            transactions: Projector::new(),
+        }
+    }
+
+    /// Copies the event logs of the local events
+    pub fn from_events(events: &EventSourcingService) -> Self {
+        Self {
+            cards: events.borrow().clone(),
+            transactions: events.borrow_transactions().clone(),
+        }
+    }
+
+    /// Merges these logs into some local events
+    pub fn merge_into(&self, events: &mut EventSourcingService) -> Result<MergeReport> {
+        events.merge(&self.cards, &self.transactions)
+    }
+}
+
+impl Default for SyncLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Checks if the name of a profile can be used on a sync server (letters, digits, `-` and `_`)
+pub fn check_profile(profile: &str) -> Result<()> {
+    let valid = !profile.is_empty()
+        && profile.len() <= MAX_PROFILE_LENGTH
+        && profile
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::InvalidProfile(profile.to_owned()))
+    }
+}
+
+/**
+Returns the URL of a profile's event logs on a sync server
+
+`GET` returns the stored logs, while `POST` merges the sent logs into them (returning the result).
+*/
+pub fn events_url(server: &str, profile: &str) -> Result<String> {
+    check_profile(profile)?;
+
+    Ok(format!(
+        "{}/profiles/{}/events",
+        server.trim().trim_end_matches('/'),
+        profile
+    ))
+}
@@ -0,0 +1,286 @@
+/*!
+Filled requests and donations, which record where the cards of a card came from
+
+Each transaction changes the `have` of its card with an update event, like a manual edit.
+The transactions themselves are kept in a separate event log (see `EventSourcingService`),
+so they don't get mixed up with the changes of the cards and survive deleting a card.
+
+The requests filled so far calibrate the `FillRate` of the profile,
+which `calc_remaining` multiplies the request sizes with.
+*/
+
+use super::{
+    error::{Error, Result},
+    types::{CardEntry, Rarity},
+};
+use chrono::{DateTime, Local};
+use serde_derive::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use uuid::Uuid;
+
+/// The lowest fill rate used for calibration, so cards never stop getting done
+pub const MIN_FILL_RATE: f64 = 0.1;
+
+/// Where cards came from or went to
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub enum TransactionKind {
+    /// A request of the player was filled (of up to `request_size` cards)
+    RequestFilled { request_size: usize },
+
+    /// The player donated cards to others
+    Donated,
+}
+
+/// A change in the amount of cards of a card, recorded in its own event log
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CardTransaction {
+    /// The UUID of the transaction itself
+    pub uuid: Uuid,
+
+    /// The UUID of the card
+    pub card: Uuid,
+
+    /// The rarity of the card
+    pub rarity: Rarity,
+
+    pub kind: TransactionKind,
+
+    /// The amount of cards received or donated
+    pub amount: usize,
+
+    /// The moment the transaction was recorded
+    pub date: DateTime<Local>,
+}
+
+// Implemented manually to distinguish between transactions based on their UUIDs
+impl PartialEq for CardTransaction {
+    fn eq(&self, other: &Self) -> bool {
+        self.uuid == other.uuid
+    }
+}
+
+impl CardTransaction {
+    pub fn new(card: &CardEntry, kind: TransactionKind, amount: usize) -> Self {
+        Self {
+            uuid: Uuid::new_v4(),
+            card: card.uuid,
+            rarity: card.rarity.clone(),
+            kind,
+            amount,
+            date: Local::now(),
+        }
+    }
+
+    /// Returns the card with its amount of cards changed by the transaction
+    pub fn apply(&self, card: &CardEntry) -> Result<CardEntry> {
+        let mut card = card.clone();
+
+        card.have = match self.kind {
+            TransactionKind::RequestFilled { .. } => card.have + self.amount,
+            TransactionKind::Donated => {
+                card.have
+                    .checked_sub(self.amount)
+                    .ok_or(Error::NotEnoughCards {
+                        have: card.have,
+                        amount: self.amount,
+                    })?
+            }
+        };
+
+        Ok(card)
+    }
+}
+
+/// The transactions of all cards of a single rarity
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct RarityStats {
+    /// The amount of filled requests
+    pub requests: usize,
+
+    /// The amount of cards received from requests
+    pub received: usize,
+
+    /// The amount of cards which could have been received from requests
+    pub capacity: usize,
+
+    /// The amount of cards donated
+    pub donated: usize,
+}
+
+impl RarityStats {
+    /// Returns the share of the requested cards actually received (if anything was requested)
+    pub fn fill_rate(&self) -> Option<f64> {
+        if self.capacity == 0 {
+            return None;
+        }
+
+        Some((self.received as f64 / self.capacity as f64).min(1.))
+    }
+}
+
+/// Sums up the transactions of each rarity
+pub fn stats_by_rarity(transactions: &[CardTransaction]) -> Vec<(Rarity, RarityStats)> {
+    Rarity::iter()
+        .map(|rarity| {
+            let mut stats = RarityStats::default();
+
+            for transaction in transactions.iter().filter(|t| t.rarity == rarity) {
+                match transaction.kind {
+                    TransactionKind::RequestFilled { request_size } => {
+                        stats.requests += 1;
+                        stats.received += transaction.amount;
+                        stats.capacity += request_size;
+                    }
+                    TransactionKind::Donated => stats.donated += transaction.amount,
+                }
+            }
+
+            (rarity, stats)
+        })
+        .collect()
+}
+
+/**
+The share of each request which actually gets filled, by rarity
+
+Defaults to completely filled requests.
+*/
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct FillRate {
+    pub common: f64,
+    pub rare: f64,
+    pub epic: f64,
+}
+
+impl FillRate {
+    /// Returns the fill rate for a given rarity (legendary cards cannot be requested)
+    pub fn get(&self, rarity: &Rarity) -> f64 {
+        match rarity {
+            Rarity::Common => self.common,
+            Rarity::Rare => self.rare,
+            Rarity::Epic => self.epic,
+            Rarity::Legendary => 0.,
+        }
+    }
+
+    /// Calibrates the fill rates using the recorded requests
+    pub fn from_transactions(transactions: &[CardTransaction]) -> Self {
+        let mut fill_rate = Self::default();
+
+        for (rarity, stats) in stats_by_rarity(transactions) {
+            let rate = match stats.fill_rate() {
+                Some(rate) => rate.max(MIN_FILL_RATE),
+                None => continue,
+            };
+
+            match rarity {
+                Rarity::Common => fill_rate.common = rate,
+                Rarity::Rare => fill_rate.rare = rate,
+                Rarity::Epic => fill_rate.epic = rate,
+                Rarity::Legendary => (),
+            }
+        }
+
+        fill_rate
+    }
+}
+
+impl Default for FillRate {
+    fn default() -> Self {
+        Self {
+            common: 1.,
+            rare: 1.,
+            epic: 1.,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(rarity: Rarity, have: usize) -> CardEntry {
+        CardEntry {
+            rarity,
+            level: 9,
+            have,
+            ..CardEntry::new()
+        }
+    }
+
+    fn filled(rarity: Rarity, request_size: usize, amount: usize) -> CardTransaction {
+        CardTransaction::new(
+            &card(rarity, 0),
+            TransactionKind::RequestFilled { request_size },
+            amount,
+        )
+    }
+
+    #[test]
+    fn applies_received_and_donated_cards() {
+        let knight = card(Rarity::Common, 5);
+
+        let received = CardTransaction::new(
+            &knight,
+            TransactionKind::RequestFilled { request_size: 40 },
+            30,
+        );
+        assert_eq!(received.apply(&knight).unwrap().have, 35);
+
+        let donated = CardTransaction::new(&knight, TransactionKind::Donated, 5);
+        assert_eq!(donated.apply(&knight).unwrap().have, 0);
+    }
+
+    #[test]
+    fn cannot_donate_more_cards_than_available() {
+        let knight = card(Rarity::Common, 5);
+        let donated = CardTransaction::new(&knight, TransactionKind::Donated, 6);
+
+        assert!(matches!(
+            donated.apply(&knight),
+            Err(Error::NotEnoughCards { have: 5, amount: 6 })
+        ));
+    }
+
+    #[test]
+    fn sums_up_the_transactions_of_each_rarity() {
+        let transactions = vec![
+            filled(Rarity::Common, 40, 30),
+            filled(Rarity::Common, 40, 40),
+            filled(Rarity::Epic, 4, 1),
+            CardTransaction::new(&card(Rarity::Common, 10), TransactionKind::Donated, 8),
+        ];
+
+        let stats = stats_by_rarity(&transactions);
+        let get = |rarity: Rarity| {
+            stats
+                .iter()
+                .find(|(r, _)| *r == rarity)
+                .map(|(_, stats)| stats.clone())
+                .unwrap()
+        };
+
+        let common = get(Rarity::Common);
+        assert_eq!(common.requests, 2);
+        assert_eq!(common.received, 70);
+        assert_eq!(common.capacity, 80);
+        assert_eq!(common.donated, 8);
+        assert_eq!(common.fill_rate(), Some(70. / 80.));
+
+        assert_eq!(get(Rarity::Epic).fill_rate(), Some(0.25));
+        assert_eq!(get(Rarity::Rare), RarityStats::default());
+        assert_eq!(get(Rarity::Rare).fill_rate(), None);
+    }
+
+    #[test]
+    fn calibrates_fill_rates_above_the_minimum() {
+        let transactions = vec![filled(Rarity::Common, 40, 20), filled(Rarity::Rare, 4, 0)];
+
+        let fill_rate = FillRate::from_transactions(&transactions);
+
+        assert_eq!(fill_rate.common, 0.5);
+        assert_eq!(fill_rate.rare, MIN_FILL_RATE);
+        // Rarities without any requests keep the default
+        assert_eq!(fill_rate.epic, FillRate::default().epic);
+    }
+}
@@ -1,8 +1,12 @@
 use serde_derive::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
 use strum_macros::{EnumIter, EnumString};
 use uuid::Uuid;
 
-use super::calc::CardData;
+use super::{
+    calc::{CardData, LevelCap},
+    error::{Error, Result},
+};
 
 #[derive(Deserialize, Clone)]
 pub struct CardEntryV1 {
@@ -35,6 +39,7 @@ impl CardEntryV1 {
             level: self.level,
             card_type: self.card_type,
             rarity: self.rarity,
+            in_deck: false,
             computed: self.computed,
         }
     }
@@ -61,6 +66,10 @@ pub struct CardEntry {
     /// The rarity of the card
     pub rarity: Rarity,
 
+    /// Whether the card is part of the player's deck
+    #[serde(default)]
+    pub in_deck: bool,
+
     /// Computed values
     #[serde(skip)]
     pub computed: Option<CardData>,
@@ -75,9 +84,27 @@ impl CardEntry {
             level: 9,
             card_type: CardType::Building,
             rarity: Rarity::Common,
+            in_deck: false,
             computed: None,
         }
     }
+
+    /// Compares all entered values, unlike `==` which only compares the UUIDs
+    pub fn same_values(&self, other: &Self) -> bool {
+        self.uuid == other.uuid
+            && self.name == other.name
+            && self.have == other.have
+            && self.level == other.level
+            && self.card_type == other.card_type
+            && self.rarity == other.rarity
+            && self.in_deck == other.in_deck
+    }
+}
+
+impl Default for CardEntry {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 // Implemented manually to distinguish between card entries based on their UUIDs
@@ -87,14 +114,16 @@ impl PartialEq for CardEntry {
     }
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Clone)]
+#[derive(Serialize, Deserialize, Debug, EnumIter, EnumString, PartialEq, Clone)]
 pub enum CardType {
     Troop,
     Spell,
     Building,
 }
 
-#[derive(Serialize, Deserialize, Debug, EnumIter, EnumString, PartialEq, Clone)]
+#[derive(
+    Serialize, Deserialize, Debug, EnumIter, EnumString, PartialEq, Eq, PartialOrd, Ord, Clone,
+)]
 pub enum Rarity {
     Common,
     Rare,
@@ -117,12 +146,32 @@ pub enum Arena {
     HogMountain,
     ElectroValley,
     SpookyTown,
+    RascalsHideout,
+    SerenityPeak,
+    MinersMine,
+    ExecutionersKitchen,
+    RoyalCrypt,
+    SilentSanctuary,
+    DragonSpa,
     LegendaryArena,
 }
 
 pub struct RequestSize {
     pub common: usize,
     pub rare: usize,
+    pub epic: usize,
+}
+
+impl RequestSize {
+    /// Returns the request size for a given rarity (legendary cards cannot be requested)
+    pub fn get(&self, rarity: &Rarity) -> usize {
+        match rarity {
+            Rarity::Common => self.common,
+            Rarity::Rare => self.rare,
+            Rarity::Epic => self.epic,
+            Rarity::Legendary => 0,
+        }
+    }
 }
 
 pub struct DonationSize {
@@ -146,6 +195,13 @@ pub fn get_donation_limit(arena: &Arena) -> usize {
         Arena::HogMountain => 360,
         Arena::ElectroValley => 360,
         Arena::SpookyTown => 360,
+        Arena::RascalsHideout => 360,
+        Arena::SerenityPeak => 360,
+        Arena::MinersMine => 360,
+        Arena::ExecutionersKitchen => 360,
+        Arena::RoyalCrypt => 360,
+        Arena::SilentSanctuary => 360,
+        Arena::DragonSpa => 360,
         Arena::LegendaryArena => 360,
     }
 }
@@ -166,6 +222,13 @@ pub fn get_donation_size(arena: &Arena) -> DonationSize {
         Arena::HogMountain => DonationSize { common: 8, rare: 1 },
         Arena::ElectroValley => DonationSize { common: 8, rare: 1 },
         Arena::SpookyTown => DonationSize { common: 8, rare: 1 },
+        Arena::RascalsHideout => DonationSize { common: 8, rare: 1 },
+        Arena::SerenityPeak => DonationSize { common: 8, rare: 1 },
+        Arena::MinersMine => DonationSize { common: 8, rare: 1 },
+        Arena::ExecutionersKitchen => DonationSize { common: 8, rare: 1 },
+        Arena::RoyalCrypt => DonationSize { common: 8, rare: 1 },
+        Arena::SilentSanctuary => DonationSize { common: 8, rare: 1 },
+        Arena::DragonSpa => DonationSize { common: 8, rare: 1 },
         Arena::LegendaryArena => DonationSize { common: 8, rare: 1 },
     }
 }
@@ -173,28 +236,82 @@ pub fn get_donation_size(arena: &Arena) -> DonationSize {
 /// Returns the request size limit for a given arena
 pub fn get_request_size(arena: &Arena) -> RequestSize {
     match arena {
-        Arena::TrainingCamp => RequestSize { common: 0, rare: 0 },
+        Arena::TrainingCamp => RequestSize {
+            common: 0,
+            rare: 0,
+            epic: 0,
+        },
         Arena::GoblinStadium | Arena::BonePit | Arena::BarbarianBowl => RequestSize {
             common: 10,
             rare: 1,
+            epic: 1,
         },
         Arena::PekkasPlayhouse | Arena::SpellValley | Arena::BuildersWorkshop => RequestSize {
             common: 20,
             rare: 2,
+            epic: 1,
         },
         Arena::RoyalArena | Arena::FrozenPeak | Arena::JungleArena => RequestSize {
             common: 30,
             rare: 3,
+            epic: 2,
+        },
+        Arena::HogMountain
+        | Arena::ElectroValley
+        | Arena::SpookyTown
+        | Arena::RascalsHideout
+        | Arena::SerenityPeak
+        | Arena::MinersMine
+        | Arena::ExecutionersKitchen
+        | Arena::RoyalCrypt
+        | Arena::SilentSanctuary
+        | Arena::DragonSpa
+        | Arena::LegendaryArena => RequestSize {
+            common: 40,
+            rare: 4,
+            epic: 4,
         },
-        Arena::HogMountain | Arena::ElectroValley | Arena::SpookyTown | Arena::LegendaryArena => {
-            RequestSize {
-                common: 40,
-                rare: 4,
-            }
-        }
     }
 }
 
+/// Returns the amount of trophies needed to reach a given arena
+pub fn get_trophy_threshold(arena: &Arena) -> usize {
+    match arena {
+        Arena::TrainingCamp => 0,
+        Arena::GoblinStadium => 0,
+        Arena::BonePit => 300,
+        Arena::BarbarianBowl => 600,
+        Arena::PekkasPlayhouse => 1000,
+        Arena::SpellValley => 1300,
+        Arena::BuildersWorkshop => 1600,
+        Arena::RoyalArena => 2000,
+        Arena::FrozenPeak => 2300,
+        Arena::JungleArena => 2600,
+        Arena::HogMountain => 3000,
+        Arena::ElectroValley => 3400,
+        Arena::SpookyTown => 3800,
+        Arena::RascalsHideout => 4200,
+        Arena::SerenityPeak => 4600,
+        Arena::MinersMine => 5000,
+        Arena::ExecutionersKitchen => 5500,
+        Arena::RoyalCrypt => 6000,
+        Arena::SilentSanctuary => 6500,
+        Arena::DragonSpa => 7000,
+        Arena::LegendaryArena => 7500,
+    }
+}
+
+impl Arena {
+    /// Infers the arena of a player from their amount of trophies
+    pub fn from_trophies(trophies: usize) -> Self {
+        Arena::iter()
+            .rev()
+            .find(|arena| get_trophy_threshold(arena) <= trophies)
+            .unwrap_or(Arena::TrainingCamp)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct RequestFrequency {
     pub common: f64,
     pub rare: f64,
@@ -202,6 +319,24 @@ pub struct RequestFrequency {
     pub legendary: f64,
 }
 
+impl RequestFrequency {
+    /// Returns the amount of requests per week for a given rarity
+    pub fn get(&self, rarity: &Rarity) -> f64 {
+        match rarity {
+            Rarity::Common => self.common,
+            Rarity::Rare => self.rare,
+            Rarity::Epic => self.epic,
+            Rarity::Legendary => self.legendary,
+        }
+    }
+}
+
+impl Default for RequestFrequency {
+    fn default() -> Self {
+        REQUEST_FREQUENCY
+    }
+}
+
 /**
 The amount of requests to place per week
 
@@ -227,56 +362,134 @@ const NEEDED_GOLD: [usize; 13] = [
     0, 5, 20, 50, 150, 400, 1000, 2000, 4000, 8000, 20000, 50000, 100000,
 ];
 
+/// The experience gained by upgrading a card from a level (by index) to the next one
+const UPGRADE_XP: [usize; 13] = [0, 4, 5, 6, 10, 25, 50, 100, 200, 400, 600, 800, 1600];
+
+/// The experience needed to advance from a King level (by index + 1) to the next one
+pub const KING_LEVEL_XP: [usize; 13] = [
+    20, 50, 100, 200, 400, 1000, 2000, 3000, 4000, 5000, 6000, 7000, 8000,
+];
+
+/// The highest King level
+pub const MAX_KING_LEVEL: usize = 14;
+
+/// The highest level any card can reach
+pub const MAX_LEVEL: usize = 13;
+
+impl Rarity {
+    /// The level at which cards of this rarity start
+    pub fn min_level(&self) -> usize {
+        1 + match self {
+            Rarity::Common => COMMON_OFFSET,
+            Rarity::Rare => RARE_OFFSET,
+            Rarity::Epic => EPIC_OFFSET,
+            Rarity::Legendary => LEGENDARY_OFFSET,
+        }
+    }
+}
+
 impl CardEntry {
+    /// Checks if the level of the card is possible for its rarity
+    pub fn check_level(&self) -> Result<()> {
+        if self.level < self.rarity.min_level() || self.level > MAX_LEVEL {
+            Err(Error::InvalidLevel {
+                level: self.level,
+                rarity: self.rarity.clone(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+
     /// Calculates the amount of required cards to upgrade to the next level (or 0 when on 13)
-    pub fn get_needed_cards(&self) -> usize {
-        if self.level == 13 {
-            return 0;
-        };
+    pub fn get_needed_cards(&self) -> Result<usize> {
+        self.check_level()?;
 
-        let use_offset = |offset: usize| {
-            if self.level < 1 {
-                panic!("Invalid level")
-            } else {
-                NEEDED_CARDS[self.level - offset]
-            }
+        if self.level == MAX_LEVEL {
+            return Ok(0);
         };
 
-        match self.rarity {
+        let use_offset = |offset: usize| NEEDED_CARDS[self.level - offset];
+
+        Ok(match self.rarity {
             Rarity::Common => use_offset(COMMON_OFFSET),
             Rarity::Rare => use_offset(RARE_OFFSET),
             Rarity::Epic => use_offset(EPIC_OFFSET),
             Rarity::Legendary => use_offset(LEGENDARY_OFFSET),
-        }
+        })
     }
 
     /// Calculates the amount of required gold to upgrade to the next level (or 0 when on 13)
-    pub fn get_needed_gold(&self) -> usize {
-        if self.level == 13 {
-            return 0;
+    pub fn get_needed_gold(&self) -> Result<usize> {
+        self.check_level()?;
+
+        if self.level == MAX_LEVEL {
+            return Ok(0);
         }
 
-        match self.rarity {
+        Ok(match self.rarity {
             Rarity::Common => NEEDED_GOLD[self.level],
             Rarity::Rare => NEEDED_GOLD[self.level],
             Rarity::Epic if self.level == 6 => 400,
             Rarity::Epic => NEEDED_GOLD[self.level],
             Rarity::Legendary if self.level == 9 => 5000,
             Rarity::Legendary => NEEDED_GOLD[self.level],
+        })
+    }
+
+    /// Calculates the experience gained by upgrading to the next level (or 0 when on 13)
+    pub fn get_upgrade_xp(&self) -> Result<usize> {
+        self.check_level()?;
+
+        if self.level == MAX_LEVEL {
+            return Ok(0);
         }
+
+        Ok(UPGRADE_XP[self.level])
     }
 
-    /// Calculates the amount of required gold to upgrade to the next level (or 0 when on 13)
-    /// as a String, formatting a `usize` not below 1000 as `{:.3}K`, with the actual number divided by 1000
-    pub fn get_needed_gold_string(&self) -> String {
-        gold_string(self.get_needed_gold())
+    /// Checks if there are enough cards to upgrade to the next level (never when on 13)
+    pub fn has_enough_cards(&self) -> Result<bool> {
+        Ok(self.level < MAX_LEVEL && self.have >= self.get_needed_cards()?)
     }
-}
 
-pub fn gold_string(number: usize) -> String {
-    if number < 1000 {
-        number.to_string()
-    } else {
-        format!("{:.3}K", number / 1000)
+    /// Calculates the share of the cards needed for the next level which are available (1 when on 13)
+    pub fn get_upgrade_progress(&self) -> Result<f64> {
+        let needed = self.get_needed_cards()?;
+
+        Ok(if needed == 0 {
+            1.
+        } else {
+            (self.have as f64 / needed as f64).min(1.)
+        })
+    }
+
+    /// Returns the card upgraded to the next level, using up the cards needed for it
+    pub fn upgraded(&self, king_level: usize) -> Result<CardEntry> {
+        let needed = self.get_needed_cards()?;
+
+        if self.level == MAX_LEVEL {
+            return Err(Error::InvalidLevel {
+                level: self.level + 1,
+                rarity: self.rarity.clone(),
+            });
+        }
+
+        if self.level_cap_at(king_level) == LevelCap::Blocked {
+            return Err(Error::LevelCapped {
+                level: self.level + 1,
+                king_level,
+            });
+        }
+
+        let mut card = self.clone();
+
+        card.have = self.have.checked_sub(needed).ok_or(Error::NotEnoughCards {
+            have: self.have,
+            amount: needed,
+        })?;
+        card.level += 1;
+
+        Ok(card)
     }
 }
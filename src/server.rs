@@ -0,0 +1,170 @@
+/*!
+A small server storing the event logs of profiles, so several devices can sync them
+
+The logs of each profile are stored as a JSON file in a directory.
+See `logic::sync::events_url` for the routes.
+*/
+
+use crate::logic::{
+    events::EventSourcingService,
+    sync::{check_profile, SyncLog},
+};
+use std::{
+    fs,
+    io::{self, Read},
+    net::SocketAddr,
+    path::PathBuf,
+};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// The largest request body accepted (in bytes)
+pub const MAX_BODY: u64 = 16 * 1024 * 1024;
+
+/// Serves the event logs of all profiles stored in a directory
+pub struct SyncServer {
+    server: Server,
+    directory: PathBuf,
+}
+
+/// A status code with a (JSON or text) body
+struct Reply {
+    status: u16,
+    body: String,
+}
+
+impl Reply {
+    fn new(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: body.into(),
+        }
+    }
+
+    fn log(log: &SyncLog) -> Self {
+        match serde_json::to_string(log) {
+            Ok(json) => Self::new(200, json),
+            Err(e) => Self::new(500, e.to_string()),
+        }
+    }
+}
+
+impl SyncServer {
+    /// Listens on an address (like `127.0.0.1:8787`), storing the logs in a directory
+    pub fn bind(address: &str, directory: impl Into<PathBuf>) -> io::Result<Self> {
+        let directory = directory.into();
+        fs::create_dir_all(&directory)?;
+
+        let server = Server::http(address).map_err(io::Error::other)?;
+
+        Ok(Self { server, directory })
+    }
+
+    /// Returns the address the server listens on (e.g. to find the port chosen for port 0)
+    pub fn address(&self) -> Option<SocketAddr> {
+        self.server.server_addr().to_ip()
+    }
+
+    /// Answers requests until the server gets dropped
+    pub fn run(&self) {
+        for request in self.server.incoming_requests() {
+            self.handle(request);
+        }
+    }
+
+    fn handle(&self, mut request: Request) {
+        let method = request.method().clone();
+        let url = request.url().to_owned();
+        let reply = self.route(&method, &url, request.as_reader());
+
+        let response = Response::from_string(reply.body)
+            .with_status_code(reply.status)
+            .with_header(header("Content-Type", "application/json"))
+            // Allow the web app to be served from anywhere
+            .with_header(header("Access-Control-Allow-Origin", "*"))
+            .with_header(header("Access-Control-Allow-Methods", "GET, POST, OPTIONS"))
+            .with_header(header("Access-Control-Allow-Headers", "Content-Type"));
+
+        // A client hanging up early doesn't concern the other clients
+        let _ = request.respond(response);
+    }
+
+    fn route(&self, method: &Method, url: &str, body: &mut dyn Read) -> Reply {
+        let path = url.split('?').next().unwrap_or_default();
+        let parts = path.trim_matches('/').split('/').collect::<Vec<_>>();
+
+        let profile = match parts.as_slice() {
+            ["profiles", profile, "events"] => *profile,
+            _ => return Reply::new(404, "Not found"),
+        };
+
+        if let Err(e) = check_profile(profile) {
+            return Reply::new(400, e.to_string());
+        }
+
+        match method {
+            Method::Options => Reply::new(204, ""),
+            Method::Get => match self.load(profile) {
+                Ok(log) => Reply::log(&log),
+                Err(e) => Reply::new(500, e.to_string()),
+            },
+            Method::Post => self.push(profile, body),
+            _ => Reply::new(405, "Method not allowed"),
+        }
+    }
+
+    /// Merges the sent logs into the stored ones, returning the result
+    fn push(&self, profile: &str, body: &mut dyn Read) -> Reply {
+        let mut json = String::new();
+        if let Err(e) = body.take(MAX_BODY).read_to_string(&mut json) {
+            return Reply::new(400, e.to_string());
+        }
+
+        let pushed: SyncLog = match serde_json::from_str(&json) {
+            Ok(log) => log,
+            Err(e) => return Reply::new(400, e.to_string()),
+        };
+
+        let stored = match self.load(profile) {
+            Ok(log) => log,
+            Err(e) => return Reply::new(500, e.to_string()),
+        };
+
+        let mut events = EventSourcingService::load(stored.cards, stored.transactions);
+        if let Err(e) = pushed.merge_into(&mut events) {
+            return Reply::new(409, e.to_string());
+        }
+
+        let merged = SyncLog::from_events(&events);
+        match self.save(profile, &merged) {
+            Ok(()) => Reply::log(&merged),
+            Err(e) => Reply::new(500, e.to_string()),
+        }
+    }
+
+    fn path(&self, profile: &str) -> PathBuf {
+        self.directory.join(format!("{}.json", profile))
+    }
+
+    /// Reads the stored logs of a profile (which are empty for new profiles)
+    fn load(&self, profile: &str) -> io::Result<SyncLog> {
+        match fs::read_to_string(self.path(profile)) {
+            Ok(json) => serde_json::from_str(&json).map_err(io::Error::from),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(SyncLog::new()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Replaces the stored logs of a profile (without leaving half-written files)
+    fn save(&self, profile: &str, log: &SyncLog) -> io::Result<()> {
+        let path = self.path(profile);
+        let temporary = path.with_extension("json.tmp");
+
+        fs::write(&temporary, serde_json::to_string(log)?)?;
+        fs::rename(temporary, path)
+    }
+}
+
+fn header(name: &str, value: &str) -> Header {
+    // Unwraps safely because all headers are ASCII
+    Header::from_bytes(name, value).unwrap()
+}
@@ -0,0 +1,132 @@
+// The service worker of cr-tools, which makes the app work offline
+//
+// The app registers it as `sw.js?version=<VERSION>` (see `components::update_notice`),
+// so every version of the app gets its own cache.
+
+const VERSION = new URL(self.location).searchParams.get("version") || "dev";
+const CACHE = `cr-tools-${VERSION}`;
+
+// A newer version of the app found by this worker, until that version's own worker takes over
+const NEXT = `${CACHE}-next`;
+
+// The page, which references all other files of the app
+const SHELL = "./";
+
+// The files referenced by the page, like the (hashed) JS, WASM and CSS files of trunk
+const ASSET = /["']([^"':]+\.(?:js|wasm|css|svg|png|webmanifest))["']/g;
+
+/// Fetches the current page and the files it references, returning the page's text
+async function cacheApp(cache) {
+  const response = await fetch(SHELL, { cache: "no-cache" });
+  if (!response.ok) {
+    throw new Error(`Cannot fetch the app: ${response.status}`);
+  }
+
+  const html = await response.clone().text();
+  const assets = [...new Set([...html.matchAll(ASSET)].map((match) => match[1]))];
+
+  await cache.addAll(assets);
+  await cache.put(SHELL, response);
+
+  return html;
+}
+
+/// Returns the newest cached page, preferring an update found by this worker
+async function newestShell() {
+  const next = await (await caches.open(NEXT)).match(SHELL);
+
+  return next || (await caches.open(CACHE)).match(SHELL);
+}
+
+/// Caches a newer version of the page (if any) in its own cache, returning whether there was one
+async function checkForUpdate() {
+  const cached = await newestShell();
+  const current = cached ? await cached.text() : null;
+
+  // Fetch into a separate cache first, so a failed update keeps the working version
+  const pending = await caches.open(`${CACHE}-pending`);
+
+  try {
+    const html = await cacheApp(pending);
+    if (html === current) {
+      return false;
+    }
+
+    // Replace an older update, keeping the cache of the running version as it is
+    await caches.delete(NEXT);
+    const next = await caches.open(NEXT);
+    for (const request of await pending.keys()) {
+      await next.put(request, await pending.match(request));
+    }
+
+    return true;
+  } finally {
+    await caches.delete(`${CACHE}-pending`);
+  }
+}
+
+self.addEventListener("install", (event) => {
+  event.waitUntil(
+    caches
+      .open(CACHE)
+      .then(cacheApp)
+      .then(() => self.skipWaiting())
+  );
+});
+
+// The page asks for updates once it listens for the answer (see `components::update_notice`)
+self.addEventListener("message", (event) => {
+  if (event.data !== "check-for-update" || !event.source) {
+    return;
+  }
+
+  event.waitUntil(
+    checkForUpdate()
+      .then((found) => {
+        if (found) {
+          event.source.postMessage("update-available");
+        }
+      })
+      .catch(() => {})
+  );
+});
+
+self.addEventListener("activate", (event) => {
+  // Remove the caches of other versions (including the updates found by their workers)
+  event.waitUntil(
+    caches
+      .keys()
+      .then((keys) => Promise.all(keys.filter((key) => key !== CACHE).map((key) => caches.delete(key))))
+      .then(() => self.clients.claim())
+  );
+});
+
+self.addEventListener("fetch", (event) => {
+  const request = event.request;
+
+  if (request.method !== "GET" || new URL(request.url).origin !== self.location.origin) {
+    return;
+  }
+
+  if (request.mode === "navigate") {
+    // Open the newest cached app right away (the page then asks for updates)
+    event.respondWith(newestShell().then((cached) => cached || fetch(request)));
+    return;
+  }
+
+  // Serve the cached files, caching the missing ones
+  event.respondWith(
+    caches.match(request).then(
+      (cached) =>
+        cached ||
+        fetch(request).then((response) => {
+          if (response.ok) {
+            const copy = response.clone();
+            caches.open(CACHE).then((cache) => cache.put(request, copy));
+          }
+
+          return response;
+        })
+    )
+  );
+});
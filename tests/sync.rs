@@ -0,0 +1,124 @@
+//! Syncs event logs through a local instance of the sync server
+#![cfg(feature = "server")]
+
+use cr_tools::{
+    logic::{
+        events::EventSourcingService,
+        sync::{events_url, SyncLog},
+        types::CardEntry,
+    },
+    server::SyncServer,
+};
+use libocc::Event;
+use std::{env, fs, path::PathBuf, thread};
+use uuid::Uuid;
+
+/// Starts a server on a free port, returning its URL and data directory
+fn start_server() -> (String, PathBuf) {
+    let directory = env::temp_dir().join(format!("cr-tools-sync-{}", Uuid::new_v4()));
+    let server = SyncServer::bind("127.0.0.1:0", &directory).unwrap();
+    let url = format!("http://{}", server.address().unwrap());
+
+    thread::spawn(move || server.run());
+
+    (url, directory)
+}
+
+/// Pushes the local logs and merges the answer into them, like the web app does
+fn sync(server: &str, profile: &str, events: &mut EventSourcingService) {
+    let pulled: SyncLog = ureq::post(&events_url(server, profile).unwrap())
+        .send_json(SyncLog::from_events(events))
+        .unwrap()
+        .into_json()
+        .unwrap();
+
+    pulled.merge_into(events).unwrap();
+}
+
+fn card(name: &str, level: usize) -> CardEntry {
+    let mut card = CardEntry::new();
+    card.name = name.to_owned();
+    card.level = level;
+    card
+}
+
+fn card_like(card: &CardEntry, level: usize) -> CardEntry {
+    let mut card = card.clone();
+    card.level = level;
+    card
+}
+
+fn names(events: &EventSourcingService) -> Vec<String> {
+    let mut names = events
+        .borrow()
+        .get_projection()
+        .iter()
+        .map(|card| card.name.clone())
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+#[test]
+fn devices_converge() {
+    let (server, directory) = start_server();
+    let mut phone = EventSourcingService::new();
+    let mut desktop = EventSourcingService::new();
+
+    phone.push(Event::create(card("Knight", 9))).unwrap();
+    sync(&server, "team", &mut phone);
+
+    desktop.push(Event::create(card("Golem", 5))).unwrap();
+    sync(&server, "team", &mut desktop);
+    sync(&server, "team", &mut phone);
+
+    assert_eq!(names(&phone), vec!["Golem", "Knight"]);
+    assert_eq!(names(&desktop), vec!["Golem", "Knight"]);
+
+    // Other profiles are stored separately
+    let mut other = EventSourcingService::new();
+    sync(&server, "someone-else", &mut other);
+    assert!(other.borrow().get_projection().is_empty());
+
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn later_update_wins() {
+    let (server, directory) = start_server();
+    let knight = card("Knight", 9);
+
+    let mut phone = EventSourcingService::new();
+    phone.push(Event::create(knight.clone())).unwrap();
+    sync(&server, "team", &mut phone);
+
+    let mut desktop = EventSourcingService::new();
+    sync(&server, "team", &mut desktop);
+
+    // Both devices change the same card
+    phone.push(Event::update(card_like(&knight, 10))).unwrap();
+    desktop.push(Event::update(card_like(&knight, 11))).unwrap();
+
+    sync(&server, "team", &mut phone);
+    let pulled: SyncLog = ureq::get(&events_url(&server, "team").unwrap())
+        .call()
+        .unwrap()
+        .into_json()
+        .unwrap();
+    let report = pulled.merge_into(&mut desktop).unwrap();
+
+    assert_eq!(report.conflicts, vec![knight.uuid]);
+    assert_eq!(desktop.borrow().get_projection()[0].level, 11);
+
+    fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn rejects_invalid_profiles() {
+    let (server, directory) = start_server();
+
+    let response = ureq::get(&format!("{}/profiles/..%2Fsecret/events", server)).call();
+    assert!(matches!(response, Err(ureq::Error::Status(400, _))));
+
+    fs::remove_dir_all(directory).unwrap();
+}